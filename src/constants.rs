@@ -8,3 +8,7 @@ pub const MAX_NUM_PRIMARY_BLOCKS: usize = 640;
 pub const MAX_NUM_SECONDARY_BLOCKS: usize = 384;
 pub const MAX_NUM_PRIMARY_TILES: usize = 640;
 pub const MAX_NUM_SECONDARY_TILES: usize = 384;
+
+// the map and 7 blocks of border on each side (8 below) have to fit in the
+// game's map buffer: (width + 15) * (height + 14) blocks
+pub const MAX_MAP_DATA_SIZE: usize = 0x2800;
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::*;
 use crate::rom::*;
+use crate::species::Species;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct EncounterEntry {
@@ -25,4 +26,7 @@ impl EncounterEntry {
         rom.write_u8(self.max_level)?;
         rom.write_u16(self.species)
     }
+    pub fn get_species(&self, rom: &mut Rom) -> Result<Species> {
+        Species::get(rom, self.species)
+    }
 }
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::encounter::*;
+use crate::error::*;
+use crate::rom::*;
+
+const ENCOUNTER_TYPES: [EncounterType; 6] = [
+    EncounterType::Grass,
+    EncounterType::Surf,
+    EncounterType::RockSmash,
+    EncounterType::OldRod,
+    EncounterType::GoodRod,
+    EncounterType::SuperRod,
+];
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncounterLocation {
+    pub bank_num: usize,
+    pub map_num: usize,
+    pub encounter_type: EncounterType,
+    #[serde(default)]
+    pub period: Option<String>, // only set for period-specific grass tables
+    pub encounter_rate: u32,
+    pub chance: u8, // sum of the chances of every slot holding the species
+    pub min_level: u8,
+    pub max_level: u8,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct EncounterIndex {
+    locations: BTreeMap<u16, Vec<EncounterLocation>>,
+}
+
+impl EncounterIndex {
+    pub fn build(rom: &mut Rom) -> Result<EncounterIndex> {
+        EncounterIndex::build_with_format(
+            rom,
+            &EncounterTablesFormat::default(),
+        )
+    }
+    pub fn build_with_format(
+        rom: &mut Rom,
+        format: &EncounterTablesFormat,
+    ) -> Result<EncounterIndex> {
+        let mut index = EncounterIndex::default();
+        for tables in EncounterTables::get_all_with_format(rom, format)? {
+            for encounter_type in ENCOUNTER_TYPES {
+                let table =
+                    match tables.get_encounter_table(rom, encounter_type)? {
+                        Some(table) => table,
+                        None => continue,
+                    };
+                index.add_table(&tables, &table, None, rom)?;
+            }
+            for period in &format.grass_periods {
+                let table = match tables
+                    .get_period_grass_encounter_table(rom, period)?
+                {
+                    Some(table) => table,
+                    None => continue,
+                };
+                index.add_table(&tables, &table, Some(period), rom)?;
+            }
+        }
+        Ok(index)
+    }
+    pub fn get(&self, species: u16) -> &[EncounterLocation] {
+        match self.locations.get(&species) {
+            Some(locations) => locations,
+            None => &[],
+        }
+    }
+    pub fn get_species(&self) -> Vec<u16> {
+        self.locations.keys().copied().collect()
+    }
+
+    fn add_table(
+        &mut self,
+        tables: &EncounterTables,
+        table: &EncounterTable,
+        period: Option<&String>,
+        rom: &mut Rom,
+    ) -> Result<()> {
+        let entries = table.get_entries(rom)?;
+        for (entry, chance) in entries.iter().zip(table.get_chances()) {
+            let locations = self.locations.entry(entry.species).or_default();
+            // a species may occupy several slots of the same table, in which
+            // case the slots are merged into a single location
+            match locations.iter_mut().find(|location| {
+                (location.bank_num, location.map_num)
+                    == (tables.bank_num, tables.map_num)
+                    && location.encounter_type == table.encounter_type
+                    && location.period.as_ref() == period
+            }) {
+                Some(location) => {
+                    location.chance += chance;
+                    location.min_level =
+                        location.min_level.min(entry.min_level);
+                    location.max_level =
+                        location.max_level.max(entry.max_level);
+                }
+                None => locations.push(EncounterLocation {
+                    bank_num: tables.bank_num,
+                    map_num: tables.map_num,
+                    encounter_type: table.encounter_type,
+                    period: period.cloned(),
+                    encounter_rate: table.encounter_rate,
+                    chance: *chance,
+                    min_level: entry.min_level,
+                    max_level: entry.max_level,
+                }),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encounter::constants::*;
+
+    const GRASS_SPECIES: [u16; 12] =
+        [25, 25, 16, 16, 16, 16, 16, 16, 16, 16, 16, 25];
+
+    // a master list with one map (bank 3, map 1) that has grass and surf
+    // encounters
+    fn build_rom() -> (Rom, EncounterTablesFormat) {
+        let mut rom = Rom::new(vec![0; 0x500]);
+        let format = EncounterTablesFormat {
+            address: 0x10,
+            grass_periods: vec![],
+        };
+        let mut tables =
+            EncounterTables::read_with_format(0x10, &mut rom, &format).unwrap();
+        (tables.bank_num, tables.map_num) = (3, 1);
+        tables.grass_address = 0x100;
+        tables.surf_address = 0x110;
+        tables.write_with_format(0x10, &mut rom, &format).unwrap();
+        rom.seek_to(0x10 + format.get_record_size()).unwrap();
+        rom.write_u16(0xFFFF).unwrap();
+        for (address, rate, entries_address) in
+            [(0x100, 20, 0x200), (0x110, 10, 0x300)]
+        {
+            let table = EncounterTable {
+                encounter_type: EncounterType::None,
+                encounter_rate: rate,
+                entries_address,
+            };
+            table.write(address, &mut rom).unwrap();
+        }
+        for (i, species) in GRASS_SPECIES.iter().enumerate() {
+            let entry = EncounterEntry {
+                min_level: 2 + i as u8,
+                max_level: 3 + i as u8,
+                species: *species,
+            };
+            entry.write(0x200 + i * 4, &mut rom).unwrap();
+        }
+        for i in 0..ENCOUNTER_TABLE_SIZE_SURF {
+            let entry = EncounterEntry {
+                min_level: 20,
+                max_level: 30,
+                species: 25,
+            };
+            entry.write(0x300 + i * 4, &mut rom).unwrap();
+        }
+        (rom, format)
+    }
+
+    #[test]
+    fn build_merges_slots_per_table() {
+        let (mut rom, format) = build_rom();
+        let index =
+            EncounterIndex::build_with_format(&mut rom, &format).unwrap();
+        assert_eq!(index.get_species(), vec![16, 25]);
+        assert!(index.get(1).is_empty());
+
+        let locations = index.get(25);
+        assert_eq!(locations.len(), 2);
+        let grass = &locations[0];
+        assert_eq!((grass.bank_num, grass.map_num), (3, 1));
+        assert_eq!(grass.encounter_type, EncounterType::Grass);
+        assert_eq!(grass.encounter_rate, 20);
+        assert_eq!(grass.chance, 20 + 20 + 1);
+        assert_eq!((grass.min_level, grass.max_level), (2, 14));
+        let surf = &locations[1];
+        assert_eq!(surf.encounter_type, EncounterType::Surf);
+        assert_eq!(surf.encounter_rate, 10);
+        assert_eq!(surf.chance, 100);
+        assert_eq!((surf.min_level, surf.max_level), (20, 30));
+
+        let locations = index.get(16);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].chance, 100 - 41);
+        assert_eq!((locations[0].min_level, locations[0].max_level), (4, 13));
+    }
+}
@@ -1,11 +1,28 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
 use crate::encounter::{constants::*, *};
 use crate::error::*;
 use crate::rom::*;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
+pub struct InvalidEncounterType(pub String);
+impl StdError for InvalidEncounterType {}
+impl fmt::Display for InvalidEncounterType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown encounter type \"{}\"!", self.0)
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum EncounterType {
+    #[default]
     None = 0,
     Grass,
     Surf,
@@ -15,13 +32,40 @@ pub enum EncounterType {
     SuperRod,
 }
 
-impl Default for EncounterType {
-    fn default() -> EncounterType { EncounterType::None }
+impl fmt::Display for EncounterType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            EncounterType::None => "none",
+            EncounterType::Grass => "grass",
+            EncounterType::Surf => "surf",
+            EncounterType::RockSmash => "rock_smash",
+            EncounterType::OldRod => "old_rod",
+            EncounterType::GoodRod => "good_rod",
+            EncounterType::SuperRod => "super_rod",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for EncounterType {
+    type Err = InvalidEncounterType;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(EncounterType::None),
+            "grass" => Ok(EncounterType::Grass),
+            "surf" => Ok(EncounterType::Surf),
+            "rock_smash" => Ok(EncounterType::RockSmash),
+            "old_rod" => Ok(EncounterType::OldRod),
+            "good_rod" => Ok(EncounterType::GoodRod),
+            "super_rod" => Ok(EncounterType::SuperRod),
+            _ => Err(InvalidEncounterType(s.to_string())),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EncounterTable {
-    #[serde(skip_serializing, skip_deserializing)]
     pub encounter_type: EncounterType,
     pub encounter_rate: u32,
     pub entries_address: usize,
@@ -43,6 +87,22 @@ impl EncounterTable {
             entries_address: rom.read_address()?,
         }))
     }
+    pub fn write(&self, address: usize, rom: &mut Rom) -> Result<()> {
+        rom.seek_to(address)?;
+        rom.write_u32(self.encounter_rate)?;
+        rom.write_address(self.entries_address)
+    }
+    pub fn get_chances(&self) -> &'static [u8] {
+        match self.encounter_type {
+            EncounterType::None => unreachable!(),
+            EncounterType::Grass => &ENCOUNTER_CHANCES_GRASS,
+            EncounterType::Surf => &ENCOUNTER_CHANCES_SURF,
+            EncounterType::RockSmash => &ENCOUNTER_CHANCES_ROCK_SMASH,
+            EncounterType::OldRod => &ENCOUNTER_CHANCES_OLD_ROD,
+            EncounterType::GoodRod => &ENCOUNTER_CHANCES_GOOD_ROD,
+            EncounterType::SuperRod => &ENCOUNTER_CHANCES_SUPER_ROD,
+        }
+    }
     pub fn get_entries(&self, rom: &mut Rom) -> Result<Vec<EncounterEntry>> {
         let encounter_table_size = match self.encounter_type {
             EncounterType::None => unreachable!(),
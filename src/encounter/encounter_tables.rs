@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::encounter::*;
@@ -6,6 +8,30 @@ use crate::rom::*;
 
 const ENCOUNTER_TABLES_ADDRESS: usize = 0x3C9CB8;
 
+// Layout of the wild encounter master list. Vanilla records are 20 bytes;
+// time-of-day (or seasonal) engines insert one extra grass pointer per
+// period right after the vanilla grass pointer, which stays the fallback.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncounterTablesFormat {
+    pub address: usize,
+    pub grass_periods: Vec<String>,
+}
+
+impl Default for EncounterTablesFormat {
+    fn default() -> EncounterTablesFormat {
+        EncounterTablesFormat {
+            address: ENCOUNTER_TABLES_ADDRESS,
+            grass_periods: vec![],
+        }
+    }
+}
+
+impl EncounterTablesFormat {
+    pub fn get_record_size(&self) -> usize {
+        20 + self.grass_periods.len() * 4
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EncounterTables {
     pub bank_num: usize,
@@ -13,6 +39,8 @@ pub struct EncounterTables {
     #[serde(skip_serializing, skip_deserializing)]
     _padding: u16,
     pub grass_address: usize,
+    #[serde(default)]
+    pub period_grass_addresses: BTreeMap<String, usize>,
     pub surf_address: usize,
     pub rock_smash_address: usize,
     pub fishing_address: usize,
@@ -24,9 +52,23 @@ impl EncounterTables {
         bank_num: usize,
         map_num: usize,
     ) -> Result<Option<EncounterTables>> {
-        let mut address = ENCOUNTER_TABLES_ADDRESS;
+        EncounterTables::get_with_format(
+            rom,
+            &EncounterTablesFormat::default(),
+            bank_num,
+            map_num,
+        )
+    }
+    pub fn get_with_format(
+        rom: &mut Rom,
+        format: &EncounterTablesFormat,
+        bank_num: usize,
+        map_num: usize,
+    ) -> Result<Option<EncounterTables>> {
+        let mut address = format.address;
         loop {
-            let tables = EncounterTables::read(address, rom)?;
+            let tables =
+                EncounterTables::read_with_format(address, rom, format)?;
             match (tables.bank_num, tables.map_num) {
                 (0xFF, 0xFF) => {
                     return Ok(None);
@@ -36,21 +78,88 @@ impl EncounterTables {
                 }
                 _ => {}
             }
-            address += 20;
+            address += format.get_record_size();
+        }
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<EncounterTables>> {
+        EncounterTables::get_all_with_format(
+            rom,
+            &EncounterTablesFormat::default(),
+        )
+    }
+    pub fn get_all_with_format(
+        rom: &mut Rom,
+        format: &EncounterTablesFormat,
+    ) -> Result<Vec<EncounterTables>> {
+        let mut address = format.address;
+        let mut all_tables = vec![];
+        loop {
+            let tables =
+                EncounterTables::read_with_format(address, rom, format)?;
+            if (tables.bank_num, tables.map_num) == (0xFF, 0xFF) {
+                return Ok(all_tables);
+            }
+            all_tables.push(tables);
+            address += format.get_record_size();
         }
     }
     pub fn read(address: usize, rom: &mut Rom) -> Result<EncounterTables> {
+        EncounterTables::read_with_format(
+            address,
+            rom,
+            &EncounterTablesFormat::default(),
+        )
+    }
+    pub fn read_with_format(
+        address: usize,
+        rom: &mut Rom,
+        format: &EncounterTablesFormat,
+    ) -> Result<EncounterTables> {
         rom.seek_to(address)?;
+        let bank_num = rom.read_u8()? as usize;
+        let map_num = rom.read_u8()? as usize;
+        let _padding = rom.read_u16()?;
+        let grass_address = rom.read_address()?;
+        let mut period_grass_addresses = BTreeMap::new();
+        for period in &format.grass_periods {
+            period_grass_addresses.insert(period.clone(), rom.read_address()?);
+        }
         Ok(EncounterTables {
-            bank_num: rom.read_u8()? as usize,
-            map_num: rom.read_u8()? as usize,
-            _padding: rom.read_u16()?,
-            grass_address: rom.read_address()?,
+            bank_num,
+            map_num,
+            _padding,
+            grass_address,
+            period_grass_addresses,
             surf_address: rom.read_address()?,
             rock_smash_address: rom.read_address()?,
             fishing_address: rom.read_address()?,
         })
     }
+    pub fn write(&self, address: usize, rom: &mut Rom) -> Result<()> {
+        self.write_with_format(address, rom, &EncounterTablesFormat::default())
+    }
+    pub fn write_with_format(
+        &self,
+        address: usize,
+        rom: &mut Rom,
+        format: &EncounterTablesFormat,
+    ) -> Result<()> {
+        rom.seek_to(address)?;
+        rom.write_u8(self.bank_num as u8)?;
+        rom.write_u8(self.map_num as u8)?;
+        rom.write_u16(self._padding)?;
+        rom.write_address(self.grass_address)?;
+        for period in &format.grass_periods {
+            let period_address = match self.period_grass_addresses.get(period) {
+                Some(period_address) => *period_address,
+                None => 0,
+            };
+            rom.write_address(period_address)?;
+        }
+        rom.write_address(self.surf_address)?;
+        rom.write_address(self.rock_smash_address)?;
+        rom.write_address(self.fishing_address)
+    }
     pub fn get_encounter_table(
         &self,
         rom: &mut Rom,
@@ -76,6 +185,18 @@ impl EncounterTables {
     ) -> Result<Option<EncounterTable>> {
         EncounterTable::read(self.grass_address, rom, EncounterType::Grass)
     }
+    pub fn get_period_grass_encounter_table(
+        &self,
+        rom: &mut Rom,
+        period: &str,
+    ) -> Result<Option<EncounterTable>> {
+        match self.period_grass_addresses.get(period) {
+            Some(address) => {
+                EncounterTable::read(*address, rom, EncounterType::Grass)
+            }
+            None => Ok(None),
+        }
+    }
     pub fn get_surf_encounter_table(
         &self,
         rom: &mut Rom,
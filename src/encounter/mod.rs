@@ -8,3 +8,6 @@ pub use self::encounter_table::*;
 
 mod encounter_tables;
 pub use self::encounter_tables::*;
+
+mod encounter_index;
+pub use self::encounter_index::*;
@@ -1,8 +1,12 @@
 use std::error::Error as StdError;
 use std::fmt;
 
+use crate::constants::MAX_MAP_DATA_SIZE;
+use crate::graphics::ImageError;
 use crate::lz77::LzError;
 use crate::mapping::InvalidBlock;
+use crate::species::InvalidLearnsetMove;
+use crate::text::TextError;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -12,8 +16,20 @@ pub enum Error {
     SeekToNullError,
     OutOfBoundsError(usize),
     InvalidAddress(usize, u32),
+    NoFreeSpace(usize),
+    MapSizeMismatch(usize, usize, usize, usize),
+    InvalidLayoutId(u16),
+    InvalidMapSection(u8),
+    RegionSizeMismatch(usize, usize, usize),
+    InvalidBorderSize(usize, usize),
+    InvalidMapSize(u32, u32),
+    InvalidPartySize(usize),
     LzError(LzError),
     InvalidBlock(InvalidBlock),
+    TextError(TextError),
+    JsonError(serde_json::Error),
+    ImageError(ImageError),
+    InvalidLearnsetMove(InvalidLearnsetMove),
 }
 
 impl StdError for Error {
@@ -22,8 +38,20 @@ impl StdError for Error {
             Error::SeekToNullError => None,
             Error::OutOfBoundsError(_) => None,
             Error::InvalidAddress(_, _) => None,
+            Error::NoFreeSpace(_) => None,
+            Error::MapSizeMismatch(_, _, _, _) => None,
+            Error::InvalidLayoutId(_) => None,
+            Error::InvalidMapSection(_) => None,
+            Error::RegionSizeMismatch(_, _, _) => None,
+            Error::InvalidBorderSize(_, _) => None,
+            Error::InvalidMapSize(_, _) => None,
+            Error::InvalidPartySize(_) => None,
             Error::LzError(err) => Some(err),
             Error::InvalidBlock(err) => Some(err),
+            Error::TextError(err) => Some(err),
+            Error::JsonError(err) => Some(err),
+            Error::ImageError(err) => Some(err),
+            Error::InvalidLearnsetMove(err) => Some(err),
         }
     }
 }
@@ -49,8 +77,60 @@ impl fmt::Display for Error {
                     bytes[0], bytes[1], bytes[2], bytes[3], address
                 )
             }
+            Error::NoFreeSpace(length) => {
+                write!(f, "Cannot find {:#x} bytes of free space!", length)
+            }
+            Error::MapSizeMismatch(width, height, map_w, map_h) => {
+                write!(
+                    f,
+                    "Cannot write {}x{} blocks to a {}x{} map!",
+                    width, height, map_w, map_h
+                )
+            }
+            Error::InvalidLayoutId(layout_id) => {
+                write!(f, "There is no layout with ID {}!", layout_id)
+            }
+            Error::InvalidMapSection(map_section) => {
+                write!(
+                    f,
+                    "Map section {:#x} is not a FRLG map section (expected 0x58 - 0xc5)!",
+                    map_section
+                )
+            }
+            Error::RegionSizeMismatch(width, height, num_blocks) => {
+                write!(
+                    f,
+                    "A {}x{} region cannot have {} blocks!",
+                    width, height, num_blocks
+                )
+            }
+            Error::InvalidBorderSize(width, height) => {
+                write!(
+                    f,
+                    "A border cannot be {}x{} (must be 1x1 to 255x255)!",
+                    width, height
+                )
+            }
+            Error::InvalidMapSize(width, height) => {
+                write!(
+                    f,
+                    "A map cannot be {}x{} (must be at least 1x1, with (width + 15) * (height + 14) at most {:#x})!",
+                    width, height, MAX_MAP_DATA_SIZE
+                )
+            }
+            Error::InvalidPartySize(party_size) => {
+                write!(
+                    f,
+                    "A party cannot have {} members (must be 1 - 6)!",
+                    party_size
+                )
+            }
             Error::LzError(err) => err.fmt(f),
             Error::InvalidBlock(err) => err.fmt(f),
+            Error::TextError(err) => err.fmt(f),
+            Error::JsonError(err) => err.fmt(f),
+            Error::ImageError(err) => err.fmt(f),
+            Error::InvalidLearnsetMove(err) => err.fmt(f),
         }
     }
 }
@@ -66,3 +146,27 @@ impl From<InvalidBlock> for Error {
         Error::InvalidBlock(err)
     }
 }
+
+impl From<TextError> for Error {
+    fn from(err: TextError) -> Self {
+        Error::TextError(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::JsonError(err)
+    }
+}
+
+impl From<ImageError> for Error {
+    fn from(err: ImageError) -> Self {
+        Error::ImageError(err)
+    }
+}
+
+impl From<InvalidLearnsetMove> for Error {
+    fn from(err: InvalidLearnsetMove) -> Self {
+        Error::InvalidLearnsetMove(err)
+    }
+}
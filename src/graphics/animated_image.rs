@@ -0,0 +1,71 @@
+use crate::graphics::*;
+
+// frames share the palette of the first frame; delays are in game frames
+// (1/60 s)
+#[derive(Clone, Debug)]
+pub struct AnimatedImage {
+    pub frames: Vec<Image>,
+    pub delays: Vec<u16>,
+}
+
+impl AnimatedImage {
+    pub fn with_color_conversion(
+        &self,
+        conversion: ColorConversion,
+    ) -> AnimatedImage {
+        AnimatedImage {
+            frames: self
+                .frames
+                .iter()
+                .map(|frame| frame.with_color_conversion(conversion))
+                .collect(),
+            delays: self.delays.clone(),
+        }
+    }
+    pub fn with_transparency(
+        &self,
+        transparency: Transparency,
+    ) -> AnimatedImage {
+        AnimatedImage {
+            frames: self
+                .frames
+                .iter()
+                .map(|frame| frame.with_transparency(transparency))
+                .collect(),
+            delays: self.delays.clone(),
+        }
+    }
+    pub fn get_png_data(&self) -> Vec<u8> {
+        let first = &self.frames[0];
+        let palette = first
+            .palette
+            .iter()
+            .flat_map(|c| c.to_rgb())
+            .collect::<Vec<u8>>();
+        let mut png = vec![];
+        {
+            let mut encoder = png::Encoder::new(
+                std::io::Cursor::new(&mut png),
+                first.width as u32,
+                first.height as u32,
+            );
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_palette(std::borrow::Cow::Owned(palette));
+            if let Some(trns) = first.get_png_trns() {
+                encoder.set_trns(trns);
+            }
+            encoder.set_compression(png::Compression::Best);
+            encoder.set_animated(self.frames.len() as u32, 0).unwrap();
+            let mut writer = encoder.write_header().unwrap();
+            for (frame, delay) in self.frames.iter().zip(&self.delays) {
+                writer.set_frame_delay(*delay, 60).unwrap();
+                writer.write_image_data(&frame.pixels).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        png
+    }
+    pub fn get_as_png(&self) -> String {
+        base64::encode(self.get_png_data())
+    }
+}
@@ -1,5 +1,25 @@
 use std::fmt;
 
+// how 5-bit GBA color channels are mapped to 8-bit channels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorConversion {
+    #[default]
+    Shift, // c << 3 (white is 248, 248, 248)
+    ShiftReplicate, // c << 3 | c >> 2 (white is 255, 255, 255)
+    GbaLcd,         // color correction of the GBA LCD (by Talarubi)
+}
+
+// GBA LCD color correction: channels go through the gamma of the LCD, are
+// mixed (the LCD bleeds colors into each other), then through the output gamma
+const LCD_GAMMA: f64 = 4.0;
+const OUT_GAMMA: f64 = 2.2;
+const LCD_MIX: [[f64; 3]; 3] = [
+    [255.0, 50.0, 0.0],  // r from r, g, b
+    [10.0, 230.0, 30.0], // g from r, g, b
+    [50.0, 10.0, 220.0], // b from r, g, b
+];
+const LCD_SCALE: f64 = 255.0 * 255.0 / 280.0;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Color {
     pub r: u8,
@@ -16,11 +36,59 @@ impl fmt::Display for Color {
 
 impl Color {
     pub fn new(value: u16) -> Color {
+        Color::with_conversion(value, ColorConversion::Shift)
+    }
+    pub fn with_conversion(value: u16, conversion: ColorConversion) -> Color {
+        let channels = [value & 31, (value >> 5) & 31, (value >> 10) & 31];
+        let [r, g, b] = match conversion {
+            ColorConversion::Shift => channels.map(|c| (c << 3) as u8),
+            ColorConversion::ShiftReplicate => {
+                channels.map(|c| (c << 3 | c >> 2) as u8)
+            }
+            ColorConversion::GbaLcd => {
+                let linear =
+                    channels.map(|c| (c as f64 / 31.0).powf(LCD_GAMMA));
+                LCD_MIX.map(|row| {
+                    let mixed = (0..3).map(|i| row[i] * linear[i]).sum::<f64>();
+                    ((mixed / 255.0).powf(1.0 / OUT_GAMMA) * LCD_SCALE).round()
+                        as u8
+                })
+            }
+        };
+        Color { r, g, b, a: 0xFF }
+    }
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 0xFF }
+    }
+    // inverse of `Color::new` (and of `ColorConversion::ShiftReplicate`)
+    pub fn to_bgr555(&self) -> u16 {
+        (self.r as u16 >> 3)
+            | (self.g as u16 >> 3) << 5
+            | (self.b as u16 >> 3) << 10
+    }
+    pub fn to_bgr555_with_conversion(
+        &self,
+        conversion: ColorConversion,
+    ) -> u16 {
+        if conversion != ColorConversion::GbaLcd {
+            return self.to_bgr555();
+        }
+        // undo the output gamma and the mixing, then the gamma of the LCD; dark
+        // colors collapse under the LCD gamma, so this is only the closest match
+        let mixed = [self.r, self.g, self.b]
+            .map(|c| (c as f64 / LCD_SCALE).powf(OUT_GAMMA) * 255.0);
+        let inverse = invert(LCD_MIX);
+        let [r, g, b] = inverse.map(|row| {
+            let linear = (0..3).map(|i| row[i] * mixed[i]).sum::<f64>();
+            (linear.clamp(0.0, 1.0).powf(1.0 / LCD_GAMMA) * 31.0).round() as u16
+        });
+        r | g << 5 | b << 10
+    }
+    // re-converts a color read with `ColorConversion::Shift`
+    pub fn convert(&self, conversion: ColorConversion) -> Color {
         Color {
-            r: ((value & 31) * 8) as u8,
-            g: (((value >> 5) & 31) * 8) as u8,
-            b: (((value >> 10) & 31) * 8) as u8,
-            a: 0xFF,
+            a: self.a,
+            ..Color::with_conversion(self.to_bgr555(), conversion)
         }
     }
     pub fn to_rgb(&self) -> [u8; 3] {
@@ -30,6 +98,55 @@ impl Color {
         ((self.r as u32) << 24)
             + ((self.g as u32) << 16)
             + ((self.b as u32) << 8)
-            + 0xFF
+            + self.a as u32
+    }
+}
+
+fn invert(m: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+        (m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]) / det
+    };
+    [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_bgr555_round_trip() {
+        for value in 0..0x8000u16 {
+            assert_eq!(Color::new(value).to_bgr555(), value);
+            let replicated =
+                Color::with_conversion(value, ColorConversion::ShiftReplicate);
+            assert_eq!(replicated.to_bgr555(), value);
+        }
+    }
+
+    #[test]
+    fn to_bgr555_channels() {
+        assert_eq!(Color::from_rgb(0xFF, 0, 0).to_bgr555(), 0x001F);
+        assert_eq!(Color::from_rgb(0, 0xFF, 0).to_bgr555(), 0x03E0);
+        assert_eq!(Color::from_rgb(0, 0, 0xFF).to_bgr555(), 0x7C00);
+        assert_eq!(Color::from_rgb(0x07, 0x07, 0x07).to_bgr555(), 0);
     }
 }
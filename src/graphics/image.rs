@@ -0,0 +1,434 @@
+use std::fmt;
+
+use crate::constants::*;
+use crate::error::*;
+use crate::graphics::*;
+use crate::lz77::*;
+use crate::rom::*;
+
+#[derive(Debug)]
+pub enum ImageError {
+    DecodingError(png::DecodingError),
+    InvalidDimensions(usize, usize, usize, usize),
+    TooManyColors(usize, usize),
+}
+impl std::error::Error for ImageError {}
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::DecodingError(err) => err.fmt(f),
+            ImageError::InvalidDimensions(width, height, exp_w, exp_h) => {
+                write!(
+                    f,
+                    "Image is {}x{} (expected {}x{})!",
+                    width, height, exp_w, exp_h
+                )
+            }
+            ImageError::TooManyColors(num_colors, max_num_colors) => {
+                write!(
+                    f,
+                    "Image has {} colors (expected at most {})!",
+                    num_colors, max_num_colors
+                )
+            }
+        }
+    }
+}
+
+impl From<png::DecodingError> for ImageError {
+    fn from(err: png::DecodingError) -> Self {
+        ImageError::DecodingError(err)
+    }
+}
+
+// what color index 0 (the transparent color on the GBA) is exported as
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Transparency {
+    #[default]
+    Opaque, // its palette color
+    Transparent,
+    Backdrop(Color), // e.g. the backdrop color of a map
+}
+
+// an indexed image, as found on the GBA
+#[derive(Clone, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+    pub palette: Vec<Color>,
+    pub transparent_index: Option<u8>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, palette: Vec<Color>) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![0; width * height],
+            palette,
+            transparent_index: None,
+        }
+    }
+    // builds an image from 4bpp tiles laid out one after another, row by row
+    pub fn from_tiles(
+        tiles_data: &[u8],
+        width: usize,
+        height: usize,
+        palette: Vec<Color>,
+    ) -> Image {
+        Image::from_tiles_with_format(
+            tiles_data,
+            width,
+            height,
+            palette,
+            TileFormat::default(),
+        )
+    }
+    pub fn from_tiles_with_format(
+        tiles_data: &[u8],
+        width: usize,
+        height: usize,
+        palette: Vec<Color>,
+        format: TileFormat,
+    ) -> Image {
+        let mut image = Image::new(width, height, palette);
+        image.pixels = decode_tiles(tiles_data, width, height, format);
+        image
+    }
+    // reads (and decompresses, if `compressed` is set) tile graphics at
+    // `address`, e.g. title screens or trainer cards
+    pub fn read(
+        address: usize,
+        rom: &mut Rom,
+        compressed: bool,
+        width: usize,
+        height: usize,
+        format: TileFormat,
+        palette: Vec<Color>,
+    ) -> Result<Image> {
+        let tiles_data = match compressed {
+            true => lz77_decompress(address, rom)?,
+            false => {
+                rom.seek_to(address)?;
+                rom.read_data(format.get_data_length(width, height))
+            }
+        };
+        Ok(Image::from_tiles_with_format(
+            &tiles_data,
+            width,
+            height,
+            palette,
+            format,
+        ))
+    }
+    // decodes a PNG into a 16-color image; indexed PNGs with at most 16 colors
+    // keep their palette order, any other PNG is quantized if `quantize` is
+    // set, with its transparent (or top-left) color at index 0
+    pub fn from_png(png_data: &[u8], quantize: bool) -> Result<Image> {
+        let decoder = png::Decoder::new(std::io::Cursor::new(png_data));
+        let mut reader = decoder.read_info().map_err(ImageError::from)?;
+        let info = reader.info();
+        if let (png::ColorType::Indexed, Some(palette)) =
+            (info.color_type, &info.palette)
+        {
+            if palette.len() / 3 <= NUM_COLORS_IN_PALETTE {
+                let palette = palette
+                    .chunks(3)
+                    .map(|c| Color::from_rgb(c[0], c[1], c[2]))
+                    .collect::<Vec<Color>>();
+                let bit_depth = info.bit_depth as usize;
+                let mut data = vec![0; reader.output_buffer_size()];
+                let frame =
+                    reader.next_frame(&mut data).map_err(ImageError::from)?;
+                let (width, height) =
+                    (frame.width as usize, frame.height as usize);
+                let mut image = Image::new(width, height, palette);
+                for y in 0..height {
+                    let row = &data[y * frame.line_size..];
+                    for x in 0..width {
+                        let bit = x * bit_depth;
+                        let byte = row[bit / 8];
+                        let shift = 8 - bit_depth - bit % 8;
+                        image.pixels[y * width + x] =
+                            (byte >> shift) & ((1 << bit_depth) - 1) as u8;
+                    }
+                }
+                return Ok(image);
+            }
+        }
+
+        let decoder = {
+            let mut decoder = png::Decoder::new(std::io::Cursor::new(png_data));
+            decoder.set_transformations(
+                png::Transformations::normalize_to_color8(),
+            );
+            decoder
+        };
+        let mut reader = decoder.read_info().map_err(ImageError::from)?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut data).map_err(ImageError::from)?;
+        let (width, height) = (frame.width as usize, frame.height as usize);
+        let channels = frame.color_type.samples();
+        let pixels = data
+            .chunks(channels)
+            .take(width * height)
+            .map(|p| match channels {
+                1 => [p[0], p[0], p[0], 0xFF],
+                2 => [p[0], p[0], p[0], p[1]],
+                3 => [p[0], p[1], p[2], 0xFF],
+                _ => [p[0], p[1], p[2], p[3]],
+            })
+            .collect::<Vec<[u8; 4]>>();
+        quantize_pixels(&pixels, width, height, quantize)
+    }
+    // encodes the image as 4bpp tiles laid out one after another, row by row
+    pub fn to_tiles(&self) -> Vec<u8> {
+        self.to_tiles_with_format(TileFormat::default())
+    }
+    pub fn to_tiles_with_format(&self, format: TileFormat) -> Vec<u8> {
+        encode_tiles(&self.pixels, self.width, self.height, format)
+    }
+    pub fn check_dimensions(&self, width: usize, height: usize) -> Result<()> {
+        if (self.width, self.height) != (width, height) {
+            Err(ImageError::InvalidDimensions(
+                self.width,
+                self.height,
+                width,
+                height,
+            ))?;
+        }
+        Ok(())
+    }
+    pub fn with_color_conversion(&self, conversion: ColorConversion) -> Image {
+        Image {
+            palette: self
+                .palette
+                .iter()
+                .map(|c| c.convert(conversion))
+                .collect(),
+            ..self.clone()
+        }
+    }
+    pub fn with_transparency(&self, transparency: Transparency) -> Image {
+        let mut image = self.clone();
+        match transparency {
+            Transparency::Opaque => image.transparent_index = None,
+            Transparency::Transparent => image.transparent_index = Some(0),
+            Transparency::Backdrop(color) => {
+                image.transparent_index = None;
+                image.palette[0] = color;
+            }
+        }
+        image
+    }
+    // the tRNS chunk of the image: the alpha of each palette color, with the
+    // transparent index fully transparent; `None` if the image is opaque
+    pub fn get_png_trns(&self) -> Option<Vec<u8>> {
+        let mut trns = self.palette.iter().map(|c| c.a).collect::<Vec<u8>>();
+        if let Some(index) = self.transparent_index {
+            let index = index as usize;
+            if trns.len() <= index {
+                trns.resize(index + 1, 0xFF);
+            }
+            trns[index] = 0;
+        }
+        while trns.last() == Some(&0xFF) {
+            trns.pop();
+        }
+        match trns.is_empty() {
+            true => None,
+            false => Some(trns),
+        }
+    }
+    pub fn get_png_data(&self) -> Vec<u8> {
+        let palette = self
+            .palette
+            .iter()
+            .flat_map(|c| c.to_rgb())
+            .collect::<Vec<u8>>();
+        let mut png = vec![];
+        {
+            let mut encoder = png::Encoder::new(
+                std::io::Cursor::new(&mut png),
+                self.width as u32,
+                self.height as u32,
+            );
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_palette(std::borrow::Cow::Owned(palette));
+            if let Some(trns) = self.get_png_trns() {
+                encoder.set_trns(trns);
+            }
+            encoder.set_compression(png::Compression::Best);
+            encoder
+                .write_header()
+                .unwrap()
+                .write_image_data(&self.pixels)
+                .unwrap();
+        }
+        png
+    }
+    pub fn get_as_png(&self) -> String {
+        base64::encode(self.get_png_data())
+    }
+}
+
+// colors are reduced to what the GBA can display (5 bits per channel) before
+// counting them, and median-cut into 15 colors (+ transparency) when needed;
+// transparent pixels become color 0, or if there are none, every pixel of the
+// color of the top-left one
+fn quantize_pixels(
+    pixels: &[[u8; 4]],
+    width: usize,
+    height: usize,
+    quantize: bool,
+) -> Result<Image> {
+    let to_gba = |p: &[u8; 4]| [p[0] >> 3, p[1] >> 3, p[2] >> 3];
+    let is_transparent = |p: &[u8; 4]| p[3] < 0x80;
+    let has_transparency = pixels.iter().any(is_transparent);
+    let background = match pixels.iter().find(|p| is_transparent(p)) {
+        Some(p) => to_gba(p),
+        None => to_gba(&pixels[0]),
+    };
+    let is_background = |p: &[u8; 4]| match has_transparency {
+        true => is_transparent(p),
+        false => to_gba(p) == background,
+    };
+
+    let mut colors: Vec<([u8; 3], usize)> = vec![];
+    for p in pixels.iter().filter(|p| !is_background(p)) {
+        let color = to_gba(p);
+        match colors.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, count)) => *count += 1,
+            None => colors.push((color, 1)),
+        }
+    }
+    let max_num_colors = NUM_COLORS_IN_PALETTE - 1;
+    if colors.len() > max_num_colors && !quantize {
+        Err(ImageError::TooManyColors(
+            colors.len() + 1,
+            NUM_COLORS_IN_PALETTE,
+        ))?;
+    }
+    let gba_palette = match colors.len() > max_num_colors {
+        true => median_cut(colors, max_num_colors),
+        false => colors.into_iter().map(|(c, _)| c).collect(),
+    };
+
+    let to_color =
+        |c: &[u8; 3]| Color::from_rgb(c[0] << 3, c[1] << 3, c[2] << 3);
+    let mut palette = vec![to_color(&background)];
+    palette.extend(gba_palette.iter().map(to_color));
+    palette.resize(NUM_COLORS_IN_PALETTE, Color::from_rgb(0, 0, 0));
+
+    let mut image = Image::new(width, height, palette);
+    for (i, p) in pixels.iter().enumerate() {
+        if is_background(p) {
+            continue;
+        }
+        let color = to_gba(p);
+        let distance = |c: &[u8; 3]| {
+            (0..3)
+                .map(|ch| (c[ch] as i32 - color[ch] as i32).pow(2))
+                .sum::<i32>()
+        };
+        let (index, _) = gba_palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| distance(c))
+            .unwrap();
+        image.pixels[i] = index as u8 + 1;
+    }
+    Ok(image)
+}
+
+fn median_cut(
+    colors: Vec<([u8; 3], usize)>,
+    num_colors: usize,
+) -> Vec<[u8; 3]> {
+    let get_range = |bucket: &Vec<([u8; 3], usize)>, ch: usize| {
+        let values = bucket.iter().map(|(c, _)| c[ch]);
+        values.clone().max().unwrap() - values.min().unwrap()
+    };
+    let mut buckets = vec![colors];
+    while buckets.len() < num_colors {
+        // split the bucket with the widest channel along that channel
+        let (bucket_num, ch, range) = buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(i, b)| (0..3).map(move |ch| (i, ch, get_range(b, ch))))
+            .max_by_key(|(_, _, range)| *range)
+            .unwrap();
+        if range == 0 {
+            break;
+        }
+        let mut bucket = buckets.swap_remove(bucket_num);
+        bucket.sort_by_key(|(c, _)| c[ch]);
+        let total: usize = bucket.iter().map(|(_, count)| count).sum();
+        let mut seen = 0;
+        let mut median = 1;
+        for (i, (_, count)) in bucket.iter().enumerate() {
+            seen += count;
+            if seen * 2 >= total {
+                median = (i + 1).clamp(1, bucket.len() - 1);
+                break;
+            }
+        }
+        let upper = bucket.split_off(median);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+    buckets
+        .iter()
+        .map(|bucket| {
+            let total: usize = bucket.iter().map(|(_, count)| count).sum();
+            let mut average = [0u8; 3];
+            for (ch, value) in average.iter_mut().enumerate() {
+                let sum: usize = bucket
+                    .iter()
+                    .map(|(c, count)| c[ch] as usize * count)
+                    .sum();
+                *value = ((sum + total / 2) / total) as u8;
+            }
+            average
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantize_keeps_opaque_pixels_of_the_transparent_color() {
+        let pixels = [
+            [0, 0, 0, 0],
+            [0, 0, 0, 0xFF],
+            [0xF8, 0, 0, 0xFF],
+            [0, 0, 0, 0],
+        ];
+        let image = quantize_pixels(&pixels, 2, 2, false).unwrap();
+        assert_eq!(image.pixels[0], 0);
+        assert_eq!(image.pixels[3], 0);
+        assert_ne!(image.pixels[1], 0);
+        assert_ne!(image.pixels[1], image.pixels[2]);
+        let black = image.palette[image.pixels[1] as usize];
+        assert_eq!(black.to_rgb(), [0, 0, 0]);
+    }
+
+    #[test]
+    fn quantize_uses_top_left_color_of_opaque_images() {
+        let pixels = [
+            [0xF8, 0xF8, 0xF8, 0xFF],
+            [0, 0, 0, 0xFF],
+            [0xF8, 0xF8, 0xF8, 0xFF],
+            [0xF8, 0, 0, 0xFF],
+        ];
+        let image = quantize_pixels(&pixels, 2, 2, false).unwrap();
+        assert_eq!(image.pixels[0], 0);
+        assert_eq!(image.pixels[2], 0);
+        assert_ne!(image.pixels[1], 0);
+        assert_ne!(image.pixels[3], 0);
+        assert_eq!(image.palette[0].to_rgb(), [0xF8, 0xF8, 0xF8]);
+    }
+}
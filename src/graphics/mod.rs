@@ -1,2 +1,14 @@
+mod animated_image;
+pub use self::animated_image::AnimatedImage;
+
 mod color;
-pub use self::color::Color;
+pub use self::color::{Color, ColorConversion};
+
+mod image;
+pub use self::image::{Image, ImageError, Transparency};
+
+mod tiles;
+pub use self::tiles::{
+    decode_tile, decode_tiles, encode_tile, encode_tiles, BitDepth, TileFormat,
+    TileLayout,
+};
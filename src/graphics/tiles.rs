@@ -0,0 +1,200 @@
+use crate::constants::*;
+
+// number of bits per pixel of tile graphics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitDepth {
+    #[default]
+    Four, // 16 colors, two pixels per byte (low nibble first)
+    Eight, // 256 colors, one pixel per byte
+}
+
+impl BitDepth {
+    pub fn get_tile_length(&self) -> usize {
+        match self {
+            BitDepth::Four => SIZE_TILE * SIZE_TILE / 2,
+            BitDepth::Eight => SIZE_TILE * SIZE_TILE,
+        }
+    }
+    pub fn get_max_colors(&self) -> usize {
+        match self {
+            BitDepth::Four => 16,
+            BitDepth::Eight => 256,
+        }
+    }
+}
+
+// how the tiles of a graphic are ordered in the data
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TileLayout {
+    #[default]
+    OneDimensional, // one after another, row by row
+    TwoDimensional, // in rows of 32 4bpp tiles (1024 bytes), like OBJ VRAM
+}
+
+const SIZE_2D_ROW: usize = 32 * SIZE_TILE * SIZE_TILE / 2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TileFormat {
+    pub bit_depth: BitDepth,
+    pub layout: TileLayout,
+}
+
+impl TileFormat {
+    pub fn new(bit_depth: BitDepth, layout: TileLayout) -> TileFormat {
+        TileFormat { bit_depth, layout }
+    }
+    // number of bytes of data needed for a `width`x`height` graphic
+    pub fn get_data_length(&self, width: usize, height: usize) -> usize {
+        let tile_length = self.bit_depth.get_tile_length();
+        let tiles_across = width.div_ceil(SIZE_TILE);
+        let tiles_down = height.div_ceil(SIZE_TILE);
+        match self.layout {
+            TileLayout::OneDimensional => {
+                tiles_across * tiles_down * tile_length
+            }
+            TileLayout::TwoDimensional if tiles_down == 0 => 0,
+            TileLayout::TwoDimensional => {
+                (tiles_down - 1) * SIZE_2D_ROW + tiles_across * tile_length
+            }
+        }
+    }
+    // offset in the data of the tile at (`tx`, `ty`)
+    fn get_tile_offset(
+        &self,
+        tx: usize,
+        ty: usize,
+        tiles_across: usize,
+    ) -> usize {
+        let tile_length = self.bit_depth.get_tile_length();
+        match self.layout {
+            TileLayout::OneDimensional => {
+                (ty * tiles_across + tx) * tile_length
+            }
+            TileLayout::TwoDimensional => ty * SIZE_2D_ROW + tx * tile_length,
+        }
+    }
+}
+
+// decodes a single tile into its 64 pixels; missing data is read as 0
+pub fn decode_tile(tile_data: &[u8], bit_depth: BitDepth) -> Vec<u8> {
+    let mut pixels = match bit_depth {
+        BitDepth::Four => tile_data
+            .iter()
+            .take(bit_depth.get_tile_length())
+            .flat_map(|byte| [byte & 0xF, byte >> 4])
+            .collect::<Vec<u8>>(),
+        BitDepth::Eight => tile_data
+            .iter()
+            .take(bit_depth.get_tile_length())
+            .copied()
+            .collect::<Vec<u8>>(),
+    };
+    pixels.resize(SIZE_TILE * SIZE_TILE, 0);
+    pixels
+}
+
+// encodes the 64 pixels of a single tile
+pub fn encode_tile(pixels: &[u8], bit_depth: BitDepth) -> Vec<u8> {
+    match bit_depth {
+        BitDepth::Four => pixels
+            .chunks(2)
+            .map(|p| (p[0] & 0xF) | (p.get(1).unwrap_or(&0) & 0xF) << 4)
+            .collect(),
+        BitDepth::Eight => pixels.to_vec(),
+    }
+}
+
+// decodes tile graphics into `width`x`height` pixels (row-major); the
+// dimensions don't have to be multiples of the tile size, the last column
+// and row of tiles are cropped
+pub fn decode_tiles(
+    tiles_data: &[u8],
+    width: usize,
+    height: usize,
+    format: TileFormat,
+) -> Vec<u8> {
+    let mut pixels = vec![0; width * height];
+    let tiles_across = width.div_ceil(SIZE_TILE);
+    let tile_length = format.bit_depth.get_tile_length();
+    for ty in 0..height.div_ceil(SIZE_TILE) {
+        for tx in 0..tiles_across {
+            let offset = format.get_tile_offset(tx, ty, tiles_across);
+            if offset >= tiles_data.len() {
+                continue;
+            }
+            let end = (offset + tile_length).min(tiles_data.len());
+            let tile = decode_tile(&tiles_data[offset..end], format.bit_depth);
+            for (i, pixel) in tile.iter().enumerate() {
+                let x = tx * SIZE_TILE + i % SIZE_TILE;
+                let y = ty * SIZE_TILE + i / SIZE_TILE;
+                if x < width && y < height {
+                    pixels[y * width + x] = *pixel;
+                }
+            }
+        }
+    }
+    pixels
+}
+
+// encodes `width`x`height` pixels (row-major) as tile graphics; pixels
+// outside of the image are encoded as 0
+pub fn encode_tiles(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    format: TileFormat,
+) -> Vec<u8> {
+    let mut tiles_data = vec![0; format.get_data_length(width, height)];
+    let tiles_across = width.div_ceil(SIZE_TILE);
+    let tile_length = format.bit_depth.get_tile_length();
+    for ty in 0..height.div_ceil(SIZE_TILE) {
+        for tx in 0..tiles_across {
+            let mut tile = vec![0; SIZE_TILE * SIZE_TILE];
+            for (i, pixel) in tile.iter_mut().enumerate() {
+                let x = tx * SIZE_TILE + i % SIZE_TILE;
+                let y = ty * SIZE_TILE + i / SIZE_TILE;
+                if x < width && y < height {
+                    *pixel = pixels[y * width + x];
+                }
+            }
+            let offset = format.get_tile_offset(tx, ty, tiles_across);
+            tiles_data[offset..offset + tile_length]
+                .copy_from_slice(&encode_tile(&tile, format.bit_depth));
+        }
+    }
+    tiles_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_tiles_round_trip() {
+        // not a multiple of the tile size, to cover the cropped tiles
+        let (width, height) = (20, 13);
+        for bit_depth in [BitDepth::Four, BitDepth::Eight] {
+            for layout in
+                [TileLayout::OneDimensional, TileLayout::TwoDimensional]
+            {
+                let format = TileFormat::new(bit_depth, layout);
+                let max_colors = bit_depth.get_max_colors();
+                let pixels = (0..width * height)
+                    .map(|i| (i * 7 % max_colors) as u8)
+                    .collect::<Vec<u8>>();
+                let data = encode_tiles(&pixels, width, height, format);
+                assert_eq!(data.len(), format.get_data_length(width, height));
+                assert_eq!(decode_tiles(&data, width, height, format), pixels);
+            }
+        }
+    }
+
+    #[test]
+    fn encode_decode_tile_round_trip() {
+        let pixels = (0..64).map(|i| i as u8 % 16).collect::<Vec<u8>>();
+        let data = encode_tile(&pixels, BitDepth::Four);
+        assert_eq!(data.len(), BitDepth::Four.get_tile_length());
+        assert_eq!(data[0], 0x10);
+        assert_eq!(decode_tile(&data, BitDepth::Four), pixels);
+    }
+}
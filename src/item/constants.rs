@@ -0,0 +1,5 @@
+pub const NUM_ITEMS: usize = 375;
+
+pub const SIZE_ITEM: usize = 44;
+pub const SIZE_ITEM_NAME: usize = 14;
+pub const MAX_ITEM_DESCRIPTION_LENGTH: usize = 0x100;
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::item::{constants::*, *};
+use crate::rom::*;
+use crate::text::*;
+
+const ITEMS_ADDRESS: usize = 0x3DB028;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Item {
+    pub item_num: u16,
+    pub name: String,
+    pub index: u16,
+    pub price: u16,
+    pub hold_effect: u8,
+    pub hold_effect_param: u8,
+    pub description_addr: usize,
+    pub importance: u8,
+    pub registrability: u8,
+    pub pocket: ItemPocket,
+    pub item_type: u8,
+    pub field_use_func_addr: usize,
+    pub battle_usage: u8,
+    pub battle_use_func_addr: usize,
+    pub secondary_id: u8,
+}
+
+impl Item {
+    pub fn get(rom: &mut Rom, item_num: u16) -> Result<Item> {
+        let address = Item::get_address(item_num);
+        let name = read_text(address, rom, SIZE_ITEM_NAME)?;
+        rom.seek_to(address + SIZE_ITEM_NAME)?;
+        Ok(Item {
+            item_num,
+            name,
+            index: rom.read_u16()?,
+            price: rom.read_u16()?,
+            hold_effect: rom.read_u8()?,
+            hold_effect_param: rom.read_u8()?,
+            description_addr: rom.read_address()?,
+            importance: rom.read_u8()?,
+            registrability: rom.read_u8()?,
+            pocket: ItemPocket::new(rom.read_u8()?),
+            item_type: rom.read_u8()?,
+            field_use_func_addr: rom.read_address()?,
+            battle_usage: (rom.read_u32()? & 0xFF) as u8,
+            battle_use_func_addr: rom.read_address()?,
+            secondary_id: (rom.read_u32()? & 0xFF) as u8,
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Item>> {
+        let mut items = vec![];
+        for item_num in 0..NUM_ITEMS {
+            items.push(Item::get(rom, item_num as u16)?);
+        }
+        Ok(items)
+    }
+    pub fn get_name(rom: &mut Rom, item_num: u16) -> Result<String> {
+        read_text(Item::get_address(item_num), rom, SIZE_ITEM_NAME)
+    }
+    pub fn get_description(&self, rom: &mut Rom) -> Result<String> {
+        read_text(self.description_addr, rom, MAX_ITEM_DESCRIPTION_LENGTH)
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        let address = Item::get_address(self.item_num);
+        write_text(address, rom, &self.name, SIZE_ITEM_NAME)?;
+        rom.seek_to(address + SIZE_ITEM_NAME)?;
+        rom.write_u16(self.index)?;
+        rom.write_u16(self.price)?;
+        rom.write_u8(self.hold_effect)?;
+        rom.write_u8(self.hold_effect_param)?;
+        rom.write_address(self.description_addr)?;
+        rom.write_u8(self.importance)?;
+        rom.write_u8(self.registrability)?;
+        rom.write_u8(self.pocket.value())?;
+        rom.write_u8(self.item_type)?;
+        rom.write_address(self.field_use_func_addr)?;
+        rom.write_u32(self.battle_usage as u32)?;
+        rom.write_address(self.battle_use_func_addr)?;
+        rom.write_u32(self.secondary_id as u32)
+    }
+
+    fn get_address(item_num: u16) -> usize {
+        ITEMS_ADDRESS + item_num as usize * SIZE_ITEM
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemPocket {
+    None,
+    Items,
+    KeyItems,
+    PokeBalls,
+    TmCase,
+    BerryPouch,
+    Unknown(u8),
+}
+
+impl ItemPocket {
+    pub fn new(value: u8) -> ItemPocket {
+        match value {
+            0 => ItemPocket::None,
+            1 => ItemPocket::Items,
+            2 => ItemPocket::KeyItems,
+            3 => ItemPocket::PokeBalls,
+            4 => ItemPocket::TmCase,
+            5 => ItemPocket::BerryPouch,
+            _ => ItemPocket::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u8 {
+        match self {
+            ItemPocket::None => 0,
+            ItemPocket::Items => 1,
+            ItemPocket::KeyItems => 2,
+            ItemPocket::PokeBalls => 3,
+            ItemPocket::TmCase => 4,
+            ItemPocket::BerryPouch => 5,
+            ItemPocket::Unknown(value) => *value,
+        }
+    }
+}
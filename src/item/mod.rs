@@ -0,0 +1,7 @@
+pub mod constants;
+
+mod item_data;
+pub use self::item_data::*;
+
+mod item_pocket;
+pub use self::item_pocket::*;
@@ -2,7 +2,12 @@ pub mod constants;
 pub mod encounter;
 pub mod error;
 pub mod graphics;
+pub mod item;
 pub mod mapping;
+pub mod moves;
 pub mod rom;
+pub mod species;
+pub mod text;
+pub mod trainer;
 
 mod lz77;
@@ -37,14 +37,14 @@ pub fn lz77_decompress(address: usize, rom: &mut Rom) -> Result<Vec<u8>> {
                 };
             }
             let is_compressed_token = (bitfield & 0x80) != 0;
-            bitfield = (bitfield << 1) & 0xFF;
+            bitfield <<= 1;
             if !is_compressed_token {
                 output[index] = rom.read_u8()?;
                 index += 1;
             } else {
                 let (run_length, run_offset) = read_compressed_token(rom)?;
                 if index < run_offset {
-                    return Err(LzError::DecompressionError)?;
+                    return Err(LzError::DecompressionError.into());
                 }
                 for j in 0..run_length {
                     output[index + j] = output[index + j - run_offset];
@@ -53,11 +53,11 @@ pub fn lz77_decompress(address: usize, rom: &mut Rom) -> Result<Vec<u8>> {
             }
         }
         if bitfield != 0 {
-            return Err(LzError::DecompressionError)?;
+            return Err(LzError::DecompressionError.into());
         }
     }
     if index != output.len() {
-        return Err(LzError::DecompressionError)?;
+        return Err(LzError::DecompressionError.into());
     }
     Ok(output)
 }
@@ -78,3 +78,93 @@ fn read_compressed_token(rom: &mut Rom) -> Result<(usize, usize)> {
     let run_offset = (((byte1 & 0xF) << 8) | byte2) + 1;
     Ok((run_length, run_offset))
 }
+
+// the data is compressed greedily; runs never start at the previous byte so
+// that the result can also be decompressed straight into VRAM
+pub fn lz77_compress(data: &[u8]) -> Vec<u8> {
+    const MIN_RUN_LENGTH: usize = 3;
+    const MAX_RUN_LENGTH: usize = 18;
+    const MIN_RUN_OFFSET: usize = 2;
+    const MAX_RUN_OFFSET: usize = 0x1000;
+
+    let mut output = vec![
+        0x10,
+        (data.len() & 0xFF) as u8,
+        ((data.len() >> 8) & 0xFF) as u8,
+        ((data.len() >> 16) & 0xFF) as u8,
+    ];
+    let mut index = 0;
+    while index < data.len() {
+        let bitfield_pos = output.len();
+        output.push(0);
+        for bit in 0..8 {
+            if index >= data.len() {
+                break;
+            }
+            let mut best_length = 0;
+            let mut best_offset = 0;
+            let max_offset = MAX_RUN_OFFSET.min(index);
+            for offset in MIN_RUN_OFFSET..=max_offset {
+                let mut length = 0;
+                while length < MAX_RUN_LENGTH
+                    && index + length < data.len()
+                    && data[index + length] == data[index + length - offset]
+                {
+                    length += 1;
+                }
+                if length > best_length {
+                    best_length = length;
+                    best_offset = offset;
+                    if length == MAX_RUN_LENGTH {
+                        break;
+                    }
+                }
+            }
+            if best_length >= MIN_RUN_LENGTH {
+                output[bitfield_pos] |= 0x80 >> bit;
+                let token =
+                    (best_length - MIN_RUN_LENGTH) << 12 | (best_offset - 1);
+                output.push((token >> 8) as u8);
+                output.push((token & 0xFF) as u8);
+                index += best_length;
+            } else {
+                output.push(data[index]);
+                index += 1;
+            }
+        }
+    }
+    while output.len() % 4 != 0 {
+        output.push(0);
+    }
+    output
+}
+
+// length of the compressed data at `address`, as read by `lz77_decompress`
+pub fn lz77_compressed_length(address: usize, rom: &mut Rom) -> Result<usize> {
+    lz77_decompress(address, rom)?;
+    Ok(rom.get_position() - address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: usize = 0x10;
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let mut rom_data = vec![0; ADDRESS];
+        rom_data.extend(lz77_compress(data));
+        let mut rom = Rom::new(rom_data);
+        lz77_decompress(ADDRESS, &mut rom).unwrap()
+    }
+
+    #[test]
+    fn compress_decompress_round_trip() {
+        let repetitive = [0x11, 0x22, 0x33, 0x44].repeat(64);
+        let varied =
+            (0..=255u8).map(|i| i.wrapping_mul(37)).collect::<Vec<_>>();
+        for data in [&repetitive[..], &varied, &[0; 1000], &[0xAB]] {
+            assert_eq!(round_trip(data), data);
+        }
+    }
+}
@@ -1,31 +1,113 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use crate::encounter::*;
 use crate::error::*;
 use crate::mapping::*;
 use crate::rom::*;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Background {
-    Normal = 0,  // middle + top
-    Covered = 2, // bottom + middle (Block is covered by Hero)
-    Triple = 3,  // https://www.pokecommunity.com/showthread.php?t=352725
-    Split = 4,   // bottom + top
+    Normal,  // middle + top
+    Covered, // bottom + middle (Block is covered by Hero)
+    Triple,  // https://www.pokecommunity.com/showthread.php?t=352725
+    Split,   // bottom + top
+    Unknown(u8),
 }
 
-#[derive(Clone, Copy, Debug)]
+impl Background {
+    pub fn new(value: u8) -> Background {
+        match value {
+            0 => Background::Normal,
+            2 => Background::Covered,
+            3 => Background::Triple,
+            4 => Background::Split,
+            _ => Background::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u8 {
+        match self {
+            Background::Normal => 0,
+            Background::Covered => 2,
+            Background::Triple => 3,
+            Background::Split => 4,
+            Background::Unknown(value) => *value,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Terrain {
-    Normal = 0,
-    Grass = 1,     // cuttable-grass
-    Water = 2,     // allowed to use rod, surf
-    Waterfall = 3, // unused in game code, but used by some blocks
+    Normal,
+    Grass,     // cuttable-grass
+    Water,     // allowed to use rod, surf
+    Waterfall, // unused in game code, but used by some blocks
+    Unknown(u8),
+}
+
+impl Terrain {
+    pub fn new(value: u8) -> Terrain {
+        match value {
+            0 => Terrain::Normal,
+            1 => Terrain::Grass,
+            2 => Terrain::Water,
+            3 => Terrain::Waterfall,
+            _ => Terrain::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u8 {
+        match self {
+            Terrain::Normal => 0,
+            Terrain::Grass => 1,
+            Terrain::Water => 2,
+            Terrain::Waterfall => 3,
+            Terrain::Unknown(value) => *value,
+        }
+    }
+}
+
+// 0x3 - 0x7 are never used in FRLG, but some hacks assign them to blocks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEncounter {
+    None,
+    Grass,
+    Surf,
+    Unknown(u8),
+}
+
+impl BlockEncounter {
+    pub fn new(value: u8) -> BlockEncounter {
+        match value {
+            0 => BlockEncounter::None,
+            1 => BlockEncounter::Grass,
+            2 => BlockEncounter::Surf,
+            _ => BlockEncounter::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u8 {
+        match self {
+            BlockEncounter::None => 0,
+            BlockEncounter::Grass => 1,
+            BlockEncounter::Surf => 2,
+            BlockEncounter::Unknown(value) => *value,
+        }
+    }
+    pub fn get_encounter_type(&self) -> EncounterType {
+        match self {
+            BlockEncounter::Grass => EncounterType::Grass,
+            BlockEncounter::Surf => EncounterType::Surf,
+            _ => EncounterType::None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum InvalidBlock {
     InvalidTerrain(u32),
     InvalidBackground(u32),
-    InvalidEncounter(u32),
+    UnusedBitsSet(u32),
 }
 impl std::error::Error for InvalidBlock {}
 impl fmt::Display for InvalidBlock {
@@ -45,17 +127,20 @@ impl fmt::Display for InvalidBlock {
                     value
                 )
             }
-            InvalidBlock::InvalidEncounter(value) => {
-                write!(
-                    f,
-                    "Unknown encounter ({:#x}) (expected 0, 1, or 2)!",
-                    value
-                )
+            InvalidBlock::UnusedBitsSet(value) => {
+                write!(f, "Unused attribute bits are set ({:#010x})!", value)
             }
         }
     }
 }
 
+// an `InvalidBlock` found while reading a tileset leniently
+#[derive(Debug)]
+pub struct BlockWarning {
+    pub block_num: usize,
+    pub warning: InvalidBlock,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Block {
     pub tiles: [Tile; 8],
@@ -67,14 +152,19 @@ pub struct Block {
     // <unused>       0x1B
     // background  0x1C - 0x1E
     // <unused>       0x1F
-    pub behavior: u16,
+    pub behavior: MetatileBehavior,
     pub terrain: Terrain,
-    pub encounter: EncounterType,
+    pub encounter: BlockEncounter,
     pub background: Background,
+    pub unused_bits: u32, // kept as is, some hacks use them
 }
 
+const UNUSED_BITS_MASK: u32 = 0x88FFC000;
+
 impl Block {
-    pub fn read(
+    // keeps unknown terrain and background values (and unused bits) as they
+    // are; see `get_warnings`
+    pub fn read_lenient(
         block_address: usize,
         block_attr_address: usize,
         rom: &mut Rom,
@@ -95,37 +185,56 @@ impl Block {
 
         rom.seek_to(block_attr_address)?;
         let value = rom.read_u32()?;
-        let behavior = (value & 0x1FF) as u16;
-        let tr = (value & 0x3E00) >> 9;
-        let terrain = match tr {
-            0 => Terrain::Normal,
-            1 => Terrain::Grass,
-            2 => Terrain::Water,
-            3 => Terrain::Waterfall,
-            _ => Err(InvalidBlock::InvalidTerrain(tr))?,
-        };
-        let en = (value & 0x7000000) >> 24;
-        let encounter = match en {
-            0 => EncounterType::None,
-            1 => EncounterType::Grass,
-            2 => EncounterType::Surf,
-            _ => return Err(InvalidBlock::InvalidEncounter(en))?,
-        };
-        let bg = (value & 0x70000000) >> 28;
-        let background = match bg {
-            0 => Background::Normal,
-            2 => Background::Covered,
-            3 => Background::Triple,
-            4 => Background::Split,
-            _ => return Err(InvalidBlock::InvalidBackground(bg))?,
-        };
-
         Ok(Block {
             tiles,
-            behavior,
-            terrain,
-            encounter,
-            background,
+            behavior: MetatileBehavior::new((value & 0x1FF) as u16),
+            terrain: Terrain::new(((value & 0x3E00) >> 9) as u8),
+            encounter: BlockEncounter::new(((value & 0x7000000) >> 24) as u8),
+            background: Background::new(((value & 0x70000000) >> 28) as u8),
+            unused_bits: value & UNUSED_BITS_MASK,
         })
     }
+    // unlike `read_lenient`, fails on unknown terrain and background values
+    pub fn read(
+        block_address: usize,
+        block_attr_address: usize,
+        rom: &mut Rom,
+    ) -> Result<Block> {
+        let block =
+            Block::read_lenient(block_address, block_attr_address, rom)?;
+        if let Terrain::Unknown(value) = block.terrain {
+            Err(InvalidBlock::InvalidTerrain(value as u32))?;
+        }
+        if let Background::Unknown(value) = block.background {
+            Err(InvalidBlock::InvalidBackground(value as u32))?;
+        }
+        Ok(block)
+    }
+    // everything about the attributes that the game doesn't expect
+    pub fn get_warnings(&self) -> Vec<InvalidBlock> {
+        let mut warnings = vec![];
+        if let Terrain::Unknown(value) = self.terrain {
+            warnings.push(InvalidBlock::InvalidTerrain(value as u32));
+        }
+        if let Background::Unknown(value) = self.background {
+            warnings.push(InvalidBlock::InvalidBackground(value as u32));
+        }
+        if self.unused_bits != 0 {
+            warnings.push(InvalidBlock::UnusedBitsSet(self.unused_bits));
+        }
+        warnings
+    }
+    pub fn get_tiles_data(&self) -> Vec<u8> {
+        self.tiles
+            .iter()
+            .flat_map(|tile| tile.value().to_le_bytes())
+            .collect()
+    }
+    pub fn get_attributes(&self) -> u32 {
+        self.behavior.value() as u32
+            | (self.terrain.value() as u32) << 9
+            | (self.encounter.value() as u32) << 24
+            | (self.background.value() as u32) << 28
+            | self.unused_bits
+    }
 }
@@ -3,24 +3,46 @@ use serde::{Deserialize, Serialize};
 use crate::error::*;
 use crate::rom::*;
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct MapBlock {
     pub block_id: u16,  // 0 - 9
     pub permission: u8, // A - B: collision; C - F: elevation
 }
 
 impl MapBlock {
-    pub fn read(address: usize, rom: &mut Rom) -> Result<Self> {
-        rom.seek_to(address)?;
-        let value = rom.read_u16()?;
-        Ok(Self {
+    pub fn new(value: u16) -> Self {
+        Self {
             block_id: value & 0x3FF,
             permission: (value >> 0xA) as u8,
-        })
+        }
+    }
+    pub fn value(&self) -> u16 {
+        self.block_id | (self.permission as u16) << 0xA
+    }
+    // non-zero collision blocks movement
+    pub fn get_collision(&self) -> u8 {
+        self.permission & 0x3
+    }
+    pub fn set_collision(&mut self, collision: u8) {
+        self.permission = (self.permission & !0x3) | (collision & 0x3);
+    }
+    pub fn is_passable(&self) -> bool {
+        self.get_collision() == 0
+    }
+    // 0 is the transition elevation, 15 lets the player walk at any elevation
+    // (e.g. bridges)
+    pub fn get_elevation(&self) -> u8 {
+        self.permission >> 2
+    }
+    pub fn set_elevation(&mut self, elevation: u8) {
+        self.permission = (self.permission & 0x3) | (elevation & 0xF) << 2;
+    }
+    pub fn read(address: usize, rom: &mut Rom) -> Result<Self> {
+        rom.seek_to(address)?;
+        Ok(Self::new(rom.read_u16()?))
     }
     pub fn write(&self, address: usize, rom: &mut Rom) -> Result<()> {
         rom.seek_to(address)?;
-        let value = self.block_id | (self.permission as u16) << 0xA;
-        rom.write_u16(value)
+        rom.write_u16(self.value())
     }
 }
@@ -0,0 +1,360 @@
+use std::collections::VecDeque;
+
+use crate::error::*;
+use crate::mapping::*;
+use crate::rom::*;
+
+// which part of a map block a flood fill matches and replaces
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillMode {
+    Block,
+    Permission,
+}
+
+// a rectangular region of map blocks, as copied from a `MapEditBuffer`
+#[derive(Clone, Debug)]
+pub struct MapRegion {
+    pub width: usize,
+    pub height: usize,
+    pub map_blocks: Vec<MapBlock>,
+}
+
+impl MapRegion {
+    // fails if the region doesn't have exactly `width`x`height` blocks
+    pub fn check_size(&self) -> Result<()> {
+        if self.map_blocks.len() != self.width * self.height {
+            return Err(Error::RegionSizeMismatch(
+                self.width,
+                self.height,
+                self.map_blocks.len(),
+            ));
+        }
+        Ok(())
+    }
+    // the block at (`x`, `y`) when the region is repeated in every direction
+    // (like the border of a map, relative to the top-left of the map)
+    pub fn get_tiled_at(&self, x: i64, y: i64) -> Option<&MapBlock> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+        self.map_blocks.get(y * self.width + x)
+    }
+}
+
+// an in-memory copy of the map blocks of a layout; edits are kept in an
+// undo/redo history and only reach the ROM on `commit`
+pub struct MapEditBuffer {
+    pub width: usize,
+    pub height: usize,
+    pub layout_addr: usize,
+    map_blocks: Vec<MapBlock>,
+    undo_stack: Vec<Vec<MapBlock>>,
+    redo_stack: Vec<Vec<MapBlock>>,
+}
+
+impl MapEditBuffer {
+    pub fn read(layout: &MapLayout, rom: &mut Rom) -> Result<MapEditBuffer> {
+        Ok(MapEditBuffer {
+            width: layout.width as usize,
+            height: layout.height as usize,
+            layout_addr: layout.address,
+            map_blocks: layout.get_map_blocks(rom)?,
+            undo_stack: vec![],
+            redo_stack: vec![],
+        })
+    }
+    pub fn get_map_blocks(&self) -> &[MapBlock] {
+        &self.map_blocks
+    }
+    pub fn get_map_block_at(&self, x: usize, y: usize) -> Option<MapBlock> {
+        match x < self.width && y < self.height {
+            true => Some(self.map_blocks[y * self.width + x]),
+            false => None,
+        }
+    }
+    // sets every block of the rectangle (clipped to the map) to `map_block`
+    pub fn paint_rect(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        map_block: MapBlock,
+    ) {
+        self.push_history();
+        for yy in y..y.saturating_add(height).min(self.height) {
+            for xx in x..x.saturating_add(width).min(self.width) {
+                self.map_blocks[yy * self.width + xx] = map_block;
+            }
+        }
+    }
+    // flood fills the area connected to (`x`, `y`) that has the same block
+    // (or permission) with the block (or permission) of `map_block`
+    pub fn fill(
+        &mut self,
+        x: usize,
+        y: usize,
+        map_block: MapBlock,
+        mode: FillMode,
+    ) {
+        let Some(start) = self.get_map_block_at(x, y) else {
+            return;
+        };
+        let matches = |b: &MapBlock| match mode {
+            FillMode::Block => b.block_id == start.block_id,
+            FillMode::Permission => b.permission == start.permission,
+        };
+        let replace = |b: &mut MapBlock| match mode {
+            FillMode::Block => b.block_id = map_block.block_id,
+            FillMode::Permission => b.permission = map_block.permission,
+        };
+        let mut replaced = start;
+        replace(&mut replaced);
+        if replaced == start {
+            return;
+        }
+        self.push_history();
+        let mut visited = vec![false; self.map_blocks.len()];
+        let mut queue = VecDeque::from([(x, y)]);
+        visited[y * self.width + x] = true;
+        while let Some((x, y)) = queue.pop_front() {
+            replace(&mut self.map_blocks[y * self.width + x]);
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if nx >= self.width || ny >= self.height {
+                    continue;
+                }
+                let i = ny * self.width + nx;
+                if !visited[i] && matches(&self.map_blocks[i]) {
+                    visited[i] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+    }
+    // copies the rectangle (clipped to the map); the region is empty if
+    // (`x`, `y`) is outside of the map
+    pub fn copy(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> MapRegion {
+        if x >= self.width || y >= self.height {
+            return MapRegion {
+                width: 0,
+                height: 0,
+                map_blocks: vec![],
+            };
+        }
+        let width = width.min(self.width.saturating_sub(x));
+        let height = height.min(self.height.saturating_sub(y));
+        let mut map_blocks = vec![];
+        for yy in y..y + height {
+            let row = yy * self.width;
+            map_blocks
+                .extend_from_slice(&self.map_blocks[row + x..row + x + width]);
+        }
+        MapRegion {
+            width,
+            height,
+            map_blocks,
+        }
+    }
+    // pastes `region` with its top-left corner at (`x`, `y`); whatever falls
+    // outside of the map is dropped, fails if the region is malformed (see
+    // `MapRegion::check_size`)
+    pub fn paste(
+        &mut self,
+        x: usize,
+        y: usize,
+        region: &MapRegion,
+    ) -> Result<()> {
+        region.check_size()?;
+        self.push_history();
+        for yy in 0..region.height.min(self.height.saturating_sub(y)) {
+            for xx in 0..region.width.min(self.width.saturating_sub(x)) {
+                self.map_blocks[(y + yy) * self.width + (x + xx)] =
+                    region.map_blocks[yy * region.width + xx];
+            }
+        }
+        Ok(())
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+    // reverts the last edit, returns whether there was one
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop() {
+            Some(map_blocks) => {
+                let current =
+                    std::mem::replace(&mut self.map_blocks, map_blocks);
+                self.redo_stack.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+    // reapplies the last undone edit, returns whether there was one
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(map_blocks) => {
+                let current =
+                    std::mem::replace(&mut self.map_blocks, map_blocks);
+                self.undo_stack.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+    // writes all map blocks to the ROM at once; the history is kept
+    //
+    // the layout is read again, as it may have been resized (and its blocks
+    // moved) since the buffer was read; fails if its size no longer matches
+    pub fn commit(&self, rom: &mut Rom) -> Result<()> {
+        let layout = MapLayout::read(self.layout_addr, rom)?;
+        let size = (layout.width as usize, layout.height as usize);
+        if size != (self.width, self.height) {
+            return Err(Error::MapSizeMismatch(
+                self.width,
+                self.height,
+                size.0,
+                size.1,
+            ));
+        }
+        let data = self
+            .map_blocks
+            .iter()
+            .flat_map(|map_block| map_block.value().to_le_bytes())
+            .collect::<Vec<u8>>();
+        rom.seek_to(layout.map_blocks_addr)?;
+        rom.write_data(&data)
+    }
+
+    fn push_history(&mut self) {
+        self.undo_stack.push(self.map_blocks.clone());
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 4x3 map whose block IDs are their indices
+    fn build_buffer() -> MapEditBuffer {
+        MapEditBuffer {
+            width: 4,
+            height: 3,
+            layout_addr: 0,
+            map_blocks: (0..12).map(MapBlock::new).collect(),
+            undo_stack: vec![],
+            redo_stack: vec![],
+        }
+    }
+
+    fn get_block_ids(buffer: &MapEditBuffer) -> Vec<u16> {
+        buffer.map_blocks.iter().map(|b| b.block_id).collect()
+    }
+
+    #[test]
+    fn copy_clips_to_the_map() {
+        let buffer = build_buffer();
+        let region = buffer.copy(2, 1, 5, 5);
+        assert_eq!((region.width, region.height), (2, 2));
+        let block_ids = region.map_blocks.iter().map(|b| b.block_id);
+        assert_eq!(block_ids.collect::<Vec<u16>>(), [6, 7, 10, 11]);
+        for (x, y) in [(4, 0), (0, 3), (4, 3), (2, 3), (100, 100)] {
+            let region = buffer.copy(x, y, 2, 2);
+            assert_eq!((region.width, region.height), (0, 0));
+            assert!(region.map_blocks.is_empty());
+        }
+    }
+
+    #[test]
+    fn paste_clips_to_the_map() {
+        let mut buffer = build_buffer();
+        let region = MapRegion {
+            width: 2,
+            height: 2,
+            map_blocks: vec![MapBlock::new(0x20); 4],
+        };
+        buffer.paste(3, 2, &region).unwrap();
+        buffer.paste(4, 0, &region).unwrap();
+        buffer.paste(0, 3, &region).unwrap();
+        let mut expected = (0..12).collect::<Vec<u16>>();
+        expected[11] = 0x20;
+        assert_eq!(get_block_ids(&buffer), expected);
+    }
+
+    #[test]
+    fn paste_rejects_invalid_regions() {
+        let mut buffer = build_buffer();
+        let region = MapRegion {
+            width: 3,
+            height: 3,
+            map_blocks: vec![MapBlock::new(0x20); 4],
+        };
+        assert!(matches!(
+            buffer.paste(0, 0, &region),
+            Err(Error::RegionSizeMismatch(3, 3, 4))
+        ));
+        assert!(!buffer.can_undo());
+        assert_eq!(get_block_ids(&buffer), (0..12).collect::<Vec<u16>>());
+    }
+
+    #[test]
+    fn fill_replaces_the_connected_area() {
+        let mut buffer = build_buffer();
+        buffer.paint_rect(0, 0, 2, 3, MapBlock::new(0x20));
+        buffer.fill(1, 1, MapBlock::new(0x21), FillMode::Block);
+        let block_ids = get_block_ids(&buffer);
+        assert_eq!(block_ids, [33, 33, 2, 3, 33, 33, 6, 7, 33, 33, 10, 11]);
+        // out of the map, or nothing to replace: no edit
+        buffer.fill(4, 0, MapBlock::new(0x22), FillMode::Block);
+        buffer.fill(0, 0, MapBlock::new(0x21), FillMode::Block);
+        assert_eq!(buffer.undo_stack.len(), 2);
+        assert_eq!(get_block_ids(&buffer), block_ids);
+    }
+
+    #[test]
+    fn fill_permission_keeps_block_ids() {
+        let mut buffer = build_buffer();
+        let mut map_block = MapBlock::new(0);
+        map_block.set_collision(1);
+        buffer.fill(0, 0, map_block, FillMode::Permission);
+        assert_eq!(get_block_ids(&buffer), (0..12).collect::<Vec<u16>>());
+        assert!(buffer.map_blocks.iter().all(|b| !b.is_passable()));
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut buffer = build_buffer();
+        assert!(!buffer.undo());
+        assert!(!buffer.redo());
+        buffer.paint_rect(0, 0, 1, 1, MapBlock::new(0x20));
+        buffer.paint_rect(1, 0, 1, 1, MapBlock::new(0x21));
+        assert!(buffer.undo());
+        assert_eq!(get_block_ids(&buffer)[..2], [0x20, 1]);
+        assert!(buffer.undo());
+        assert_eq!(get_block_ids(&buffer)[..2], [0, 1]);
+        assert!(!buffer.can_undo());
+        assert!(buffer.redo());
+        assert_eq!(get_block_ids(&buffer)[..2], [0x20, 1]);
+        // a new edit drops what was undone
+        buffer.paint_rect(2, 0, 1, 1, MapBlock::new(0x22));
+        assert!(!buffer.can_redo());
+        assert_eq!(get_block_ids(&buffer)[..3], [0x20, 1, 0x22]);
+    }
+}
@@ -5,11 +5,19 @@ use crate::error::*;
 use crate::mapping::*;
 use crate::rom::*;
 
+const SIZE_MAP_HEADER: usize = 0x1C;
+// FRLG map sections (map names and region map spots) start with Kanto's, the
+// ones before are RSE's, unused in FRLG
+const FIRST_MAP_SECTION_FR: u8 = 0x58; // MAPSEC_PALLET_TOWN
+const MAP_SECTION_NONE_FR: u8 = 0xC5;
+
 pub struct MapHeader {
     pub bank_num: usize,
     pub map_num: usize,
     pub layout_addr: usize,
     pub events_addr: usize,
+    pub layout_id: u16, // 1-based index in the layout table
+    pub map_section: u8,
     // and many more other fields irrelevant for now
 }
 
@@ -27,16 +35,62 @@ impl MapHeader {
         map_num: usize,
     ) -> Result<MapHeader> {
         rom.seek_to(address)?;
+        let layout_addr = rom.read_address()?;
+        let events_addr = rom.read_address()?;
+        rom.seek_to(address + 0x12)?;
         Ok(MapHeader {
-            layout_addr: rom.read_address()?,
-            events_addr: rom.read_address()?,
+            layout_addr,
+            events_addr,
+            layout_id: rom.read_u16()?,
+            map_section: rom.read_u8()?,
             bank_num,
             map_num,
         })
     }
+    // allocates a map header using layout `layout_id` and map section
+    // `map_section` (its name and region map spot), with no events, no map
+    // scripts and no connections (every other field is 0); returns its
+    // address
+    pub fn allocate(
+        rom: &mut Rom,
+        layout_id: u16,
+        map_section: u8,
+    ) -> Result<usize> {
+        if !(FIRST_MAP_SECTION_FR..=MAP_SECTION_NONE_FR).contains(&map_section)
+        {
+            return Err(Error::InvalidMapSection(map_section));
+        }
+        let layout_addr = rom.get_map_layout_address(layout_id)?;
+        // object event, warp, coord event and bg event counts and pointers
+        let events_addr = rom.allocate(&[0; 20])?;
+        // a map script table with only the terminator
+        let map_scripts_addr = rom.allocate(&[0; 4])?;
+        let address = rom.allocate(&[0; SIZE_MAP_HEADER])?;
+        rom.seek_to(address)?;
+        rom.write_address(layout_addr)?;
+        rom.write_address(events_addr)?;
+        rom.write_address(map_scripts_addr)?;
+        rom.write_address(0)?; // connections
+        rom.seek_to(address + 0x12)?;
+        rom.write_u16(layout_id)?;
+        rom.write_u8(map_section)?;
+        Ok(address)
+    }
     pub fn get_map_layout(&self, rom: &mut Rom) -> Result<MapLayout> {
         MapLayout::read(self.layout_addr, rom)
     }
+    pub fn get_object_events(&self, rom: &mut Rom) -> Result<Vec<ObjectEvent>> {
+        rom.seek_to(self.events_addr)?;
+        let object_event_count = rom.read_u8()? as usize;
+        rom.seek_to(self.events_addr + 4)?;
+        let object_events_addr = rom.read_address()?;
+        let mut object_events = vec![];
+        for i in 0..object_event_count {
+            object_events
+                .push(ObjectEvent::read(object_events_addr + i * 24, rom)?);
+        }
+        Ok(object_events)
+    }
     pub fn get_map_name(&self, _rom: &mut Rom) -> String {
         unimplemented!();
     }
@@ -4,7 +4,61 @@ use crate::graphics::*;
 use crate::mapping::*;
 use crate::rom::*;
 
+const NUM_ELEVATIONS: usize = 16;
+const ELEVATION_COLORS: [(u8, u8, u8); NUM_ELEVATIONS] = [
+    (0xFF, 0xFF, 0xFF), // 0: transition
+    (0x00, 0x00, 0xFF),
+    (0x00, 0x80, 0xFF),
+    (0x00, 0xFF, 0xFF),
+    (0x00, 0xFF, 0x80),
+    (0x00, 0xFF, 0x00),
+    (0x80, 0xFF, 0x00),
+    (0xFF, 0xFF, 0x00),
+    (0xFF, 0x80, 0x00),
+    (0xFF, 0x00, 0x80),
+    (0xFF, 0x00, 0xFF),
+    (0x80, 0x00, 0xFF),
+    (0x80, 0x80, 0x80),
+    (0x80, 0x40, 0x00),
+    (0x00, 0x80, 0x80),
+    (0x00, 0x00, 0x00), // 15: multi-level (e.g. bridges)
+];
+
+// which part of a map stays in place when it is resized
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // fractions (in halves) of the size difference added left of and above
+    // the map
+    fn get_weights(&self) -> (i64, i64) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Center => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+}
+
 pub struct MapLayout {
+    pub address: usize,
     pub width: u32,
     pub height: u32,
     pub border_blocks_addr: usize,
@@ -19,6 +73,7 @@ impl MapLayout {
     pub fn read(address: usize, rom: &mut Rom) -> Result<MapLayout> {
         rom.seek_to(address)?;
         Ok(MapLayout {
+            address,
             width: rom.read_u32()?,
             height: rom.read_u32()?,
             border_blocks_addr: rom.read_address()?,
@@ -29,6 +84,56 @@ impl MapLayout {
             border_height: rom.read_u8()?,
         })
     }
+    // allocates a new `width`x`height` layout filled with `fill_block` and
+    // registers it in the layout table; returns it with its layout ID
+    //
+    // the sizes are checked first, so nothing is allocated if they're invalid
+    pub fn create(
+        rom: &mut Rom,
+        width: u32,
+        height: u32,
+        fill_block: MapBlock,
+        border: &MapRegion,
+        pri_tileset_addr: usize,
+        sec_tileset_addr: usize,
+    ) -> Result<(MapLayout, u16)> {
+        check_map_size(width, height)?;
+        check_border(border)?;
+        let map_blocks_data = fill_block
+            .value()
+            .to_le_bytes()
+            .repeat(width as usize * height as usize);
+        let border_data = border
+            .map_blocks
+            .iter()
+            .flat_map(|map_block| map_block.value().to_le_bytes())
+            .collect::<Vec<u8>>();
+        let layout = MapLayout {
+            address: rom.allocate(&[0; 28])?,
+            width,
+            height,
+            border_blocks_addr: rom.allocate(&border_data)?,
+            map_blocks_addr: rom.allocate(&map_blocks_data)?,
+            pri_tileset_addr,
+            sec_tileset_addr,
+            border_width: border.width as u8,
+            border_height: border.height as u8,
+        };
+        layout.write(rom)?;
+        let layout_id = rom.register_map_layout(layout.address)?;
+        Ok((layout, layout_id))
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        rom.seek_to(self.address)?;
+        rom.write_u32(self.width)?;
+        rom.write_u32(self.height)?;
+        rom.write_address(self.border_blocks_addr)?;
+        rom.write_address(self.map_blocks_addr)?;
+        rom.write_address(self.pri_tileset_addr)?;
+        rom.write_address(self.sec_tileset_addr)?;
+        rom.write_u8(self.border_width)?;
+        rom.write_u8(self.border_height)
+    }
     pub fn get_map_blocks(&self, rom: &mut Rom) -> Result<Vec<MapBlock>> {
         let mut mapblocks: Vec<MapBlock> = vec![];
         for y in 0..self.height {
@@ -60,6 +165,50 @@ impl MapLayout {
             self.map_blocks_addr + (y * 2 * self.width as usize) + (x * 2);
         map_block.write(map_block_addr, rom)
     }
+    // resizes the map, keeping its blocks in place relative to `anchor` and
+    // filling new space with `fill_block`; the block data is reallocated
+    // (moved to free space when it grows) and the layout header rewritten
+    pub fn resize(
+        &mut self,
+        rom: &mut Rom,
+        new_width: u32,
+        new_height: u32,
+        anchor: Anchor,
+        fill_block: MapBlock,
+    ) -> Result<()> {
+        let map_blocks = self.get_map_blocks(rom)?;
+        let (weight_x, weight_y) = anchor.get_weights();
+        let offset_x = (new_width as i64 - self.width as i64) * weight_x / 2;
+        let offset_y = (new_height as i64 - self.height as i64) * weight_y / 2;
+        let mut new_map_blocks =
+            vec![fill_block; new_width as usize * new_height as usize];
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                let (new_x, new_y) = (x + offset_x, y + offset_y);
+                if new_x < 0
+                    || new_y < 0
+                    || new_x >= new_width as i64
+                    || new_y >= new_height as i64
+                {
+                    continue;
+                }
+                new_map_blocks[(new_y * new_width as i64 + new_x) as usize] =
+                    map_blocks[(y * self.width as i64 + x) as usize];
+            }
+        }
+        let data = new_map_blocks
+            .iter()
+            .flat_map(|map_block| map_block.value().to_le_bytes())
+            .collect::<Vec<u8>>();
+        self.map_blocks_addr =
+            rom.reallocate(self.map_blocks_addr, map_blocks.len() * 2, &data)?;
+        self.width = new_width;
+        self.height = new_height;
+        self.write(rom)
+    }
+    pub fn get_edit_buffer(&self, rom: &mut Rom) -> Result<MapEditBuffer> {
+        MapEditBuffer::read(self, rom)
+    }
     pub fn get_pri_tileset(&self, rom: &mut Rom) -> Result<MapTileset> {
         MapTileset::read(self.pri_tileset_addr, rom, MAX_NUM_PRIMARY_BLOCKS)
     }
@@ -77,88 +226,503 @@ impl MapLayout {
             .try_into()
             .unwrap())
     }
+    // palette 0, color 0 is what shows through where no tile is drawn
+    pub fn get_backdrop_color(&self, rom: &mut Rom) -> Result<Color> {
+        Ok(self.get_palettes(rom)?[0][0])
+    }
     pub fn get_tiles_data(&self, rom: &mut Rom) -> Result<Vec<Vec<u8>>> {
         let pri_tiles_data = self.get_pri_tileset(rom)?.get_tiles_data(rom);
         let sec_tiles_data = self.get_sec_tileset(rom)?.get_tiles_data(rom);
-        Ok([&pri_tiles_data?[..], &sec_tiles_data?[..]]
-            .concat()
-            .try_into()
-            .unwrap())
+        Ok([&pri_tiles_data?[..], &sec_tiles_data?[..]].concat())
     }
     pub fn get_blocks(&self, rom: &mut Rom) -> Result<Vec<Block>> {
         let pri_blocks = self.get_pri_tileset(rom)?.get_blocks(rom);
         let sec_blocks = self.get_sec_tileset(rom)?.get_blocks(rom);
         Ok([pri_blocks, sec_blocks].concat())
     }
+    pub fn get_blocksheet(&self, rom: &mut Rom) -> Result<Image> {
+        let tiles_data = self.get_tiles_data(rom)?;
+        self.render_blocksheet(rom, &tiles_data)
+    }
     pub fn get_blocksheet_as_png(&self, rom: &mut Rom) -> Result<String> {
+        Ok(self.get_blocksheet(rom)?.get_as_png())
+    }
+    pub fn get_map_image(&self, rom: &mut Rom) -> Result<Image> {
+        let tiles_data = self.get_tiles_data(rom)?;
+        self.render_map(rom, &tiles_data)
+    }
+    pub fn get_map_image_as_png(&self, rom: &mut Rom) -> Result<String> {
+        Ok(self.get_map_image(rom)?.get_as_png())
+    }
+    pub fn get_map_image_with_border(
+        &self,
+        rom: &mut Rom,
+        margin: usize,
+    ) -> Result<Image> {
+        let tiles_data = self.get_tiles_data(rom)?;
+        self.render_map_with_border(rom, &tiles_data, margin)
+    }
+    pub fn get_map_image_with_border_as_png(
+        &self,
+        rom: &mut Rom,
+        margin: usize,
+    ) -> Result<String> {
+        Ok(self.get_map_image_with_border(rom, margin)?.get_as_png())
+    }
+    pub fn get_border(&self, rom: &mut Rom) -> Result<MapRegion> {
+        let width = self.border_width as usize;
+        let height = self.border_height as usize;
+        let mut map_blocks = vec![];
+        for i in 0..width * height {
+            map_blocks
+                .push(MapBlock::read(self.border_blocks_addr + i * 2, rom)?);
+        }
+        Ok(MapRegion {
+            width,
+            height,
+            map_blocks,
+        })
+    }
+    // writes the border blocks (reallocating them if the border grows) and
+    // rewrites the layout header with the new border size
+    pub fn set_border(
+        &mut self,
+        rom: &mut Rom,
+        border: &MapRegion,
+    ) -> Result<()> {
+        check_border(border)?;
+        let old_length =
+            self.border_width as usize * self.border_height as usize * 2;
+        let data = border
+            .map_blocks
+            .iter()
+            .flat_map(|map_block| map_block.value().to_le_bytes())
+            .collect::<Vec<u8>>();
+        self.border_blocks_addr =
+            rom.reallocate(self.border_blocks_addr, old_length, &data)?;
+        self.border_width = border.width as u8;
+        self.border_height = border.height as u8;
+        self.write(rom)
+    }
+    // semi-transparent overlay (to be drawn over the map image) with a color
+    // per elevation, a cross on blocks that can't be walked on and a grid
+    pub fn get_permission_overlay(&self, rom: &mut Rom) -> Result<Image> {
+        let mut palette = ELEVATION_COLORS
+            .iter()
+            .map(|&(r, g, b)| Color { r, g, b, a: 0x80 })
+            .collect::<Vec<Color>>();
+        palette.push(Color::from_rgb(0xFF, 0x00, 0x00)); // blocked
+        palette.push(Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0x80,
+        }); // grid
+        const BLOCKED: u8 = NUM_ELEVATIONS as u8;
+        const GRID: u8 = BLOCKED + 1;
+
+        let width = self.width as usize * SIZE_BLOCK;
+        let height = self.height as usize * SIZE_BLOCK;
+        let mut image = Image::new(width, height, palette);
+        for (i, map_block) in self.get_map_blocks(rom)?.iter().enumerate() {
+            let dx = (i % self.width as usize) * SIZE_BLOCK;
+            let dy = (i / self.width as usize) * SIZE_BLOCK;
+            for y in 0..SIZE_BLOCK {
+                for x in 0..SIZE_BLOCK {
+                    let on_cross = x == y || x == SIZE_BLOCK - 1 - y;
+                    let pixel = if x == 0 || y == 0 {
+                        GRID
+                    } else if on_cross && !map_block.is_passable() {
+                        BLOCKED
+                    } else {
+                        map_block.get_elevation()
+                    };
+                    image.pixels[(dy + y) * width + (dx + x)] = pixel;
+                }
+            }
+        }
+        Ok(image)
+    }
+    pub fn get_permission_overlay_as_png(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<String> {
+        Ok(self.get_permission_overlay(rom)?.get_as_png())
+    }
+    pub fn get_animations(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<Vec<TilesetAnimation>> {
+        let pri_animations = self.get_pri_tileset(rom)?.get_animations(rom)?;
+        let sec_animations = self.get_sec_tileset(rom)?.get_animations(rom)?;
+        Ok([pri_animations, sec_animations].concat())
+    }
+    pub fn get_animated_blocksheet(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<AnimatedImage> {
+        self.animate(rom, MapLayout::render_blocksheet)
+    }
+    pub fn get_animated_blocksheet_as_png(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<String> {
+        Ok(self.get_animated_blocksheet(rom)?.get_as_png())
+    }
+    // the map cycling through all frames of its tileset animations, with each
+    // frame shown as long as it is in game
+    pub fn get_animated_map(&self, rom: &mut Rom) -> Result<AnimatedImage> {
+        self.animate(rom, MapLayout::render_map)
+    }
+    pub fn get_animated_map_as_png(&self, rom: &mut Rom) -> Result<String> {
+        Ok(self.get_animated_map(rom)?.get_as_png())
+    }
+
+    fn animate(
+        &self,
+        rom: &mut Rom,
+        render: fn(&MapLayout, &mut Rom, &[Vec<u8>]) -> Result<Image>,
+    ) -> Result<AnimatedImage> {
+        let tiles_data = self.get_tiles_data(rom)?;
+        let animations = self.get_animations(rom)?;
+        let mut frames = vec![];
+        let mut delays = vec![];
+        for (tick, delay) in TilesetAnimation::get_steps(&animations) {
+            let mut tiles_data = tiles_data.clone();
+            for animation in &animations {
+                animation.apply_at(rom, tick, &mut tiles_data)?;
+            }
+            frames.push(render(self, rom, &tiles_data)?);
+            delays.push(delay as u16);
+        }
+        Ok(AnimatedImage { frames, delays })
+    }
+    fn render_map(
+        &self,
+        rom: &mut Rom,
+        tiles_data: &[Vec<u8>],
+    ) -> Result<Image> {
+        self.render_map_with_border(rom, tiles_data, 0)
+    }
+    // renders the map with `margin` blocks of border around it on each side
+    fn render_map_with_border(
+        &self,
+        rom: &mut Rom,
+        tiles_data: &[Vec<u8>],
+        margin: usize,
+    ) -> Result<Image> {
         let palette = self
             .get_palettes(rom)?
             .iter()
             .flat_map(|pal| *pal)
-            .flat_map(|c| c.to_rgb())
-            .collect::<Vec<u8>>();
+            .collect::<Vec<Color>>();
+        // attributes don't matter for drawing, so don't stop at unknown ones
+        let (pri_blocks, _) =
+            self.get_pri_tileset(rom)?.get_blocks_lenient(rom)?;
+        let (sec_blocks, _) =
+            self.get_sec_tileset(rom)?.get_blocks_lenient(rom)?;
+        let map_blocks = self.get_map_blocks(rom)?;
+        let border = self.get_border(rom)?;
+
+        let (map_width, map_height) = (self.width as i64, self.height as i64);
+        let blocks_across = self.width as usize + margin * 2;
+        let blocks_down = self.height as usize + margin * 2;
+        let mut image = Image::new(
+            blocks_across * SIZE_BLOCK,
+            blocks_down * SIZE_BLOCK,
+            palette,
+        );
+        for by in 0..blocks_down {
+            for bx in 0..blocks_across {
+                let x = bx as i64 - margin as i64;
+                let y = by as i64 - margin as i64;
+                let map_block =
+                    if x >= 0 && y >= 0 && x < map_width && y < map_height {
+                        map_blocks.get((y * map_width + x) as usize)
+                    } else {
+                        border.get_tiled_at(x, y)
+                    };
+                let Some(map_block) = map_block else {
+                    continue;
+                };
+                let block_id = map_block.block_id as usize;
+                let block = match block_id < MAX_NUM_PRIMARY_BLOCKS {
+                    true => pri_blocks.get(block_id),
+                    false => sec_blocks.get(block_id - MAX_NUM_PRIMARY_BLOCKS),
+                };
+                if let Some(block) = block {
+                    let dx = bx * SIZE_BLOCK;
+                    let dy = by * SIZE_BLOCK;
+                    draw_block(&mut image, block, tiles_data, dx, dy);
+                }
+            }
+        }
+        Ok(image)
+    }
+
+    fn render_blocksheet(
+        &self,
+        rom: &mut Rom,
+        tiles_data: &[Vec<u8>],
+    ) -> Result<Image> {
+        let palette = self
+            .get_palettes(rom)?
+            .iter()
+            .flat_map(|pal| *pal)
+            .collect::<Vec<Color>>();
 
         const NUM_BLOCKS_ACROSS: usize = 8;
-        let blocks = self.get_blocks(rom)?;
+        // as for the map, unknown attributes mustn't cut the sheet short (and
+        // shift every following block)
+        let (pri_blocks, _) =
+            self.get_pri_tileset(rom)?.get_blocks_lenient(rom)?;
+        let (sec_blocks, _) =
+            self.get_sec_tileset(rom)?.get_blocks_lenient(rom)?;
+        let blocks = [pri_blocks, sec_blocks].concat();
         let num_blocks = blocks.len();
         let width = NUM_BLOCKS_ACROSS * SIZE_BLOCK;
-        let height = ((num_blocks + NUM_BLOCKS_ACROSS - 1) / NUM_BLOCKS_ACROSS)
-            * SIZE_BLOCK;
-
-        let tiles_data = self.get_tiles_data(rom)?;
+        let height = num_blocks.div_ceil(NUM_BLOCKS_ACROSS) * SIZE_BLOCK;
 
-        let mut data = vec![0u8; (width * height) as usize];
+        let mut image = Image::new(width, height, palette);
         for (block_id, block) in blocks.iter().enumerate() {
             let dx = (block_id % NUM_BLOCKS_ACROSS) * SIZE_BLOCK;
             let dy = (block_id / NUM_BLOCKS_ACROSS) * SIZE_BLOCK;
-            for (tile_num, tile) in block.tiles.iter().enumerate() {
-                let tile_id = if (tile.tile_id as usize) < tiles_data.len() {
-                    tile.tile_id as usize
-                } else {
-                    0
-                };
-                let tile_data = tiles_data[tile_id]
-                    .iter()
-                    .flat_map(|byte| [byte & 0xF, byte >> 4])
-                    .collect::<Vec<u8>>();
-                let dxx = (tile_num % 2) * SIZE_TILE;
-                let dyy = ((tile_num % 4) / 2) * SIZE_TILE;
-                for (i, pixel) in tile_data.iter().enumerate() {
-                    if *pixel == 0 {
-                        continue;
-                    }
-                    let pixel =
-                        pixel + tile.palette_id * NUM_COLORS_IN_PALETTE as u8;
-                    let dxxx = match tile.h_flip {
-                        true => SIZE_TILE - (i % SIZE_TILE) - 1,
-                        false => i % SIZE_TILE,
-                    };
-                    let dyyy = match tile.v_flip {
-                        true => SIZE_TILE - (i / SIZE_TILE) - 1,
-                        false => i / SIZE_TILE,
-                    };
-                    let index =
-                        (dy + dyy + dyyy) * NUM_BLOCKS_ACROSS * SIZE_BLOCK
-                            + (dx + dxx + dxxx);
-                    data[index] = pixel;
-                }
+            draw_block(&mut image, block, tiles_data, dx, dy);
+        }
+        Ok(image)
+    }
+}
+
+fn check_map_size(width: u32, height: u32) -> Result<()> {
+    let data_size = (width as u64 + 15) * (height as u64 + 14);
+    if width == 0 || height == 0 || data_size > MAX_MAP_DATA_SIZE as u64 {
+        return Err(Error::InvalidMapSize(width, height));
+    }
+    Ok(())
+}
+
+// the border size is stored in a byte each way, and an empty border would
+// allocate no data
+fn check_border(border: &MapRegion) -> Result<()> {
+    border.check_size()?;
+    let is_valid_size = |size: usize| (1..=u8::MAX as usize).contains(&size);
+    if !is_valid_size(border.width) || !is_valid_size(border.height) {
+        return Err(Error::InvalidBorderSize(border.width, border.height));
+    }
+    Ok(())
+}
+
+fn draw_block(
+    image: &mut Image,
+    block: &Block,
+    tiles_data: &[Vec<u8>],
+    dx: usize,
+    dy: usize,
+) {
+    for (tile_num, tile) in block.tiles.iter().enumerate() {
+        let tile_id = if (tile.tile_id as usize) < tiles_data.len() {
+            tile.tile_id as usize
+        } else {
+            0
+        };
+        let tile_data = decode_tile(&tiles_data[tile_id], BitDepth::Four);
+        let dxx = (tile_num % 2) * SIZE_TILE;
+        let dyy = ((tile_num % 4) / 2) * SIZE_TILE;
+        for (i, pixel) in tile_data.iter().enumerate() {
+            if *pixel == 0 {
+                continue;
             }
+            let pixel = pixel + tile.palette_id * NUM_COLORS_IN_PALETTE as u8;
+            let dxxx = match tile.h_flip {
+                true => SIZE_TILE - (i % SIZE_TILE) - 1,
+                false => i % SIZE_TILE,
+            };
+            let dyyy = match tile.v_flip {
+                true => SIZE_TILE - (i / SIZE_TILE) - 1,
+                false => i / SIZE_TILE,
+            };
+            let index = (dy + dyy + dyyy) * image.width + (dx + dxx + dxxx);
+            image.pixels[index] = pixel;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILL_BLOCK_ID: u16 = 0x3FF;
+
+    fn build_layout() -> MapLayout {
+        MapLayout {
+            address: 0x10,
+            width: 1,
+            height: 1,
+            border_blocks_addr: 0x40,
+            map_blocks_addr: 0x50,
+            pri_tileset_addr: 0,
+            sec_tileset_addr: 0,
+            border_width: 2,
+            border_height: 2,
         }
+    }
+
+    fn build_border(width: usize, height: usize, len: usize) -> MapRegion {
+        MapRegion {
+            width,
+            height,
+            map_blocks: vec![MapBlock::new(1); len],
+        }
+    }
+
+    // a 3x2 map whose block IDs are their indices, with some free space
+    fn build_resizable_layout() -> (Rom, MapLayout) {
+        let mut rom_data = vec![0; FREE_SPACE_START];
+        rom_data.extend([0xFF; 0x100]);
+        let mut rom = Rom::new(rom_data);
+        let mut layout = build_layout();
+        (layout.width, layout.height) = (3, 2);
+        layout.write(&mut rom).unwrap();
+        for i in 0..6 {
+            let (x, y) = (i % 3, i / 3);
+            let map_block = MapBlock::new(i as u16);
+            layout.set_map_block_at(&mut rom, x, y, &map_block).unwrap();
+        }
+        (rom, layout)
+    }
+
+    // the block IDs of the map, row by row ("." for the fill block)
+    fn get_rows(rom: &mut Rom, layout: &MapLayout) -> Vec<String> {
+        let layout = MapLayout::read(layout.address, rom).unwrap();
+        let map_blocks = layout.get_map_blocks(rom).unwrap();
+        map_blocks
+            .chunks(layout.width as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|b| match b.block_id {
+                        FILL_BLOCK_ID => '.',
+                        block_id => (b'0' + block_id as u8) as char,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn resize(
+        width: u32,
+        height: u32,
+        anchor: Anchor,
+    ) -> (Rom, MapLayout, Vec<String>) {
+        let (mut rom, mut layout) = build_resizable_layout();
+        let fill_block = MapBlock::new(FILL_BLOCK_ID);
+        layout
+            .resize(&mut rom, width, height, anchor, fill_block)
+            .unwrap();
+        let rows = get_rows(&mut rom, &layout);
+        (rom, layout, rows)
+    }
+
+    #[test]
+    fn resize_grows_around_the_anchor() {
+        let (_, layout, rows) = resize(5, 4, Anchor::TopLeft);
+        assert_eq!((layout.width, layout.height), (5, 4));
+        assert_eq!(rows, ["012..", "345..", ".....", "....."]);
+        let (_, _, rows) = resize(5, 4, Anchor::Center);
+        assert_eq!(rows, [".....", ".012.", ".345.", "....."]);
+        let (_, _, rows) = resize(5, 4, Anchor::BottomRight);
+        assert_eq!(rows, [".....", ".....", "..012", "..345"]);
+        let (_, _, rows) = resize(4, 3, Anchor::Bottom);
+        assert_eq!(rows, ["....", "012.", "345."]);
+    }
+
+    #[test]
+    fn resize_shrinks_around_the_anchor() {
+        let (_, layout, rows) = resize(1, 1, Anchor::TopLeft);
+        assert_eq!((layout.width, layout.height), (1, 1));
+        assert_eq!(rows, ["0"]);
+        let (_, _, rows) = resize(1, 2, Anchor::Center);
+        assert_eq!(rows, ["1", "4"]);
+        let (_, _, rows) = resize(2, 1, Anchor::BottomRight);
+        assert_eq!(rows, ["45"]);
+    }
+
+    #[test]
+    fn resize_round_trip_keeps_blocks_in_place() {
+        for anchor in [Anchor::TopLeft, Anchor::Center, Anchor::BottomRight] {
+            let (mut rom, mut layout, _) = resize(7, 6, anchor);
+            let fill_block = MapBlock::new(FILL_BLOCK_ID);
+            layout.resize(&mut rom, 3, 2, anchor, fill_block).unwrap();
+            assert_eq!(get_rows(&mut rom, &layout), ["012", "345"]);
+        }
+    }
+
+    #[test]
+    fn set_border_rejects_invalid_borders() {
+        let mut rom = Rom::new(vec![0; 0x100]);
+        let mut layout = build_layout();
+        layout.write(&mut rom).unwrap();
+        let data = rom.get_data();
+        for (width, height, len) in [(2, 2, 3), (2, 1, 4)] {
+            let border = build_border(width, height, len);
+            assert!(matches!(
+                layout.set_border(&mut rom, &border),
+                Err(Error::RegionSizeMismatch(w, h, l))
+                    if (w, h, l) == (width, height, len)
+            ));
+        }
+        for (width, height) in [(0, 0), (0, 2), (256, 1)] {
+            let border = build_border(width, height, width * height);
+            assert!(matches!(
+                layout.set_border(&mut rom, &border),
+                Err(Error::InvalidBorderSize(w, h))
+                    if (w, h) == (width, height)
+            ));
+        }
+        assert_eq!(rom.get_data(), data);
+        assert_eq!((layout.border_width, layout.border_height), (2, 2));
+    }
+
+    #[test]
+    fn create_rejects_invalid_sizes_before_allocating() {
+        let mut rom = Rom::new(vec![0; 0x100]);
+        let fill_block = MapBlock::new(1);
+        let border = build_border(2, 2, 4);
+        for (width, height) in [(0, 10), (10, 0), (100, 100), (u32::MAX, 1)] {
+            assert!(matches!(
+                MapLayout::create(
+                    &mut rom, width, height, fill_block, &border, 0, 0
+                ),
+                Err(Error::InvalidMapSize(w, h)) if (w, h) == (width, height)
+            ));
+        }
+        let border = build_border(2, 2, 5);
+        assert!(matches!(
+            MapLayout::create(&mut rom, 20, 20, fill_block, &border, 0, 0),
+            Err(Error::RegionSizeMismatch(2, 2, 5))
+        ));
+        let border = build_border(300, 1, 300);
+        assert!(matches!(
+            MapLayout::create(&mut rom, 20, 20, fill_block, &border, 0, 0),
+            Err(Error::InvalidBorderSize(300, 1))
+        ));
+        assert_eq!(rom.get_data(), vec![0; 0x100]);
+    }
 
-        let mut png = vec![];
-        {
-            let cursor = std::io::Cursor::new(&mut png);
-            let mut encoder =
-                png::Encoder::new(cursor, width as u32, height as u32);
-            encoder.set_color(png::ColorType::Indexed);
-            encoder.set_palette(std::borrow::Cow::Owned(palette));
-            encoder.set_compression(png::Compression::Best);
-            encoder
-                .write_header()
-                .unwrap()
-                .write_image_data(&data)
-                .unwrap();
-        }
-        Ok(base64::encode(png))
+    #[test]
+    fn set_border_writes_blocks_and_size() {
+        let mut rom = Rom::new(vec![0; 0x100]);
+        let mut layout = build_layout();
+        layout.write(&mut rom).unwrap();
+        let border = MapRegion {
+            width: 2,
+            height: 1,
+            map_blocks: vec![MapBlock::new(1), MapBlock::new(2)],
+        };
+        layout.set_border(&mut rom, &border).unwrap();
+        let layout = MapLayout::read(0x10, &mut rom).unwrap();
+        assert_eq!((layout.border_width, layout.border_height), (2, 1));
+        assert_eq!(layout.border_blocks_addr, 0x40);
+        let border = layout.get_border(&mut rom).unwrap();
+        assert_eq!(border.map_blocks, [MapBlock::new(1), MapBlock::new(2)]);
     }
 }
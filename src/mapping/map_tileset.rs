@@ -6,6 +6,7 @@ use crate::mapping::*;
 use crate::rom::*;
 
 pub struct MapTileset {
+    pub address: usize,
     pub is_compressed: bool,
     pub use_sec_palettes: bool,
     pub padding: u16,
@@ -25,6 +26,7 @@ impl MapTileset {
     ) -> Result<MapTileset> {
         rom.seek_to(address)?;
         Ok(MapTileset {
+            address,
             is_compressed: rom.read_u8()? != 0,
             use_sec_palettes: rom.read_u8()? != 0,
             padding: rom.read_u16()?,
@@ -36,6 +38,65 @@ impl MapTileset {
             max_block_count,
         })
     }
+    // allocates a new (compressed) tileset, `tiles_data` being its 4bpp tiles;
+    // the tileset has no animations
+    pub fn create(
+        rom: &mut Rom,
+        is_secondary: bool,
+        tiles_data: &[u8],
+        palettes: &[[Color; NUM_COLORS_IN_PALETTE]; NUM_PALETTES_IN_TILESET],
+        blocks: &[Block],
+    ) -> Result<MapTileset> {
+        let max_block_count = match is_secondary {
+            true => MAX_NUM_SECONDARY_BLOCKS,
+            false => MAX_NUM_PRIMARY_BLOCKS,
+        };
+        let palettes_data = palettes
+            .iter()
+            .flatten()
+            .flat_map(|color| color.to_bgr555().to_le_bytes())
+            .collect::<Vec<u8>>();
+        let blockmap_data = blocks
+            .iter()
+            .take(max_block_count)
+            .flat_map(|block| block.get_tiles_data())
+            .collect::<Vec<u8>>();
+        let block_attributes_data = blocks
+            .iter()
+            .take(max_block_count)
+            .flat_map(|block| block.get_attributes().to_le_bytes())
+            .collect::<Vec<u8>>();
+        // blocks are followed right away by their attributes, as in vanilla
+        // tilesets, so that `get_block_count` finds the number of blocks
+        let blockmap_addr =
+            rom.allocate(&[blockmap_data, block_attributes_data].concat())?;
+        let tileset = MapTileset {
+            address: rom.allocate(&[0; 24])?,
+            is_compressed: true,
+            use_sec_palettes: is_secondary,
+            padding: 0,
+            tilemap_addr: rom.allocate(&lz77_compress(tiles_data))?,
+            palettes_addr: rom.allocate(&palettes_data)?,
+            blockmap_addr,
+            tileset_callback_addr: 0,
+            block_attributes_addr: blockmap_addr
+                + blocks.len().min(max_block_count) * 16,
+            max_block_count,
+        };
+        tileset.write(rom)?;
+        Ok(tileset)
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        rom.seek_to(self.address)?;
+        rom.write_u8(self.is_compressed as u8)?;
+        rom.write_u8(self.use_sec_palettes as u8)?;
+        rom.write_u16(self.padding)?;
+        rom.write_address(self.tilemap_addr)?;
+        rom.write_address(self.palettes_addr)?;
+        rom.write_address(self.blockmap_addr)?;
+        rom.write_address(self.tileset_callback_addr)?;
+        rom.write_address(self.block_attributes_addr)
+    }
     pub fn get_tiles_data(&self, rom: &mut Rom) -> Result<Vec<Vec<u8>>> {
         let data = match self.is_compressed {
             true => lz77_decompress(self.tilemap_addr, rom)?,
@@ -44,7 +105,7 @@ impl MapTileset {
                 rom.read_data(self.max_block_count * 0x20)
             }
         };
-        let tile_length = SIZE_TILE * SIZE_TILE / 2;
+        let tile_length = BitDepth::Four.get_tile_length();
         Ok(data.chunks(tile_length).map(|x| x.to_vec()).collect())
     }
     pub fn get_palettes(
@@ -62,20 +123,40 @@ impl MapTileset {
         }
         Ok(palettes.try_into().unwrap())
     }
+    // the game doesn't store how many blocks a tileset has, so this is the
+    // number of blocks (and of block attributes) that fit before the next
+    // piece of tileset data, up to `max_block_count`
+    pub fn get_block_count(&self) -> usize {
+        let addresses = [
+            self.tilemap_addr,
+            self.palettes_addr,
+            self.blockmap_addr,
+            self.tileset_callback_addr & !1,
+            self.block_attributes_addr,
+        ];
+        let space_after = |start: usize| {
+            addresses
+                .iter()
+                .filter(|&&address| address > start)
+                .min()
+                .map(|end| end - start)
+        };
+        let mut block_count = self.max_block_count;
+        if let Some(space) = space_after(self.blockmap_addr) {
+            block_count = block_count.min(space / 16);
+        }
+        if let Some(space) = space_after(self.block_attributes_addr) {
+            block_count = block_count.min(space / 4);
+        }
+        block_count
+    }
+    // stops at the first block with unknown attribute values, see
+    // `get_blocks_lenient`
     pub fn get_blocks(&self, rom: &mut Rom) -> Vec<Block> {
-        // because there is no way of knowing exactly how many blocks a tileset
-        // has, just keep trying to read blocks until:
-        // - the block's address comes after the offset for block attributes (no
-        //   longer reading blocks but block attributes instead), or
-        // - the block read is invalid, or
-        // - `max_block_count` is reached
         let mut blocks = vec![];
-        for i in 0..self.max_block_count {
+        for i in 0..self.get_block_count() {
             let block_addr = self.blockmap_addr + (i * 16);
             let block_attr_addr = self.block_attributes_addr + (i * 4);
-            if block_addr >= self.block_attributes_addr {
-                break;
-            }
             match Block::read(block_addr, block_attr_addr, rom) {
                 Ok(block) => {
                     blocks.push(block);
@@ -87,57 +168,52 @@ impl MapTileset {
         }
         blocks
     }
+    // reads all `get_block_count` blocks, keeping unknown attribute values;
+    // also returns the warnings of every block
+    pub fn get_blocks_lenient(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<(Vec<Block>, Vec<BlockWarning>)> {
+        let mut blocks = vec![];
+        let mut warnings = vec![];
+        for i in 0..self.get_block_count() {
+            let block_addr = self.blockmap_addr + (i * 16);
+            let block_attr_addr = self.block_attributes_addr + (i * 4);
+            let block = Block::read_lenient(block_addr, block_attr_addr, rom)?;
+            for warning in block.get_warnings() {
+                warnings.push(BlockWarning {
+                    block_num: i,
+                    warning,
+                });
+            }
+            blocks.push(block);
+        }
+        Ok((blocks, warnings))
+    }
+    pub fn get_animations(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<Vec<TilesetAnimation>> {
+        TilesetAnimation::read(self.tileset_callback_addr, rom)
+    }
+    pub fn get_tilesheet(
+        &self,
+        rom: &mut Rom,
+        palette_id: usize,
+    ) -> Result<Image> {
+        const NUM_TILES_ACROSS: usize = 16;
+        let tiles_data = self.get_tiles_data(rom)?.concat();
+        let num_tiles = tiles_data.len() / BitDepth::Four.get_tile_length();
+        let width = NUM_TILES_ACROSS * SIZE_TILE;
+        let height = num_tiles.div_ceil(NUM_TILES_ACROSS) * SIZE_TILE;
+        let palette = self.get_palettes(rom)?[palette_id].to_vec();
+        Ok(Image::from_tiles(&tiles_data, width, height, palette))
+    }
     pub fn get_tilesheet_as_png(
         &self,
         rom: &mut Rom,
         palette_id: usize,
     ) -> Result<String> {
-        const NUM_TILES_ACROSS: usize = 16;
-        let tiles_data = self.get_tiles_data(rom)?;
-        let num_tiles = tiles_data.len();
-        let width = NUM_TILES_ACROSS;
-        let height = (num_tiles + width - 1) / width;
-
-        let mut data =
-            vec![0u8; (width * height * SIZE_TILE * SIZE_TILE) as usize];
-        for (tile_id, tile_data) in tiles_data.iter().enumerate() {
-            let tile_data = tile_data
-                .iter()
-                .flat_map(|byte| [byte & 0xF, byte >> 4])
-                .collect::<Vec<u8>>();
-            let dx = (tile_id % NUM_TILES_ACROSS) * SIZE_TILE;
-            let dy = (tile_id / NUM_TILES_ACROSS) * SIZE_TILE;
-            for (i, pixel) in tile_data.iter().enumerate() {
-                let dxx = i % SIZE_TILE;
-                let dyy = i / SIZE_TILE;
-                let index = (dy + dyy) * width * SIZE_TILE + (dx + dxx);
-                data[index] = *pixel;
-            }
-        }
-
-        let palette = self.get_palettes(rom)?[palette_id]
-            .iter()
-            .flat_map(|c| c.to_rgb())
-            .collect::<Vec<u8>>();
-
-        let mut png = vec![];
-        {
-            let image_width = (width * SIZE_TILE) as u32;
-            let image_height = (height * SIZE_TILE) as u32;
-            let mut encoder = png::Encoder::new(
-                std::io::Cursor::new(&mut png),
-                image_width,
-                image_height,
-            );
-            encoder.set_color(png::ColorType::Indexed);
-            encoder.set_palette(std::borrow::Cow::Owned(palette));
-            encoder.set_compression(png::Compression::Best);
-            encoder
-                .write_header()
-                .unwrap()
-                .write_image_data(&data)
-                .unwrap();
-        }
-        Ok(base64::encode(png))
+        Ok(self.get_tilesheet(rom, palette_id)?.get_as_png())
     }
 }
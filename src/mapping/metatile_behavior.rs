@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+
+// the behavior IDs of FRLG (from pokefirered's metatile_behaviors.h); IDs
+// that are unused in FRLG (and IDs of hacks) are kept as `Unknown`
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MetatileBehavior {
+    #[default]
+    Normal,
+    TallGrass,
+    Cave,
+    RunningDisallowed,
+    IndoorEncounter,
+    MountainTop,
+    PondWater,
+    FastWater,
+    DeepWater,
+    Waterfall,
+    OceanWater,
+    Puddle,
+    ShallowWater,
+    UnderwaterBlockedAbove,
+    UnusedWater,
+    CyclingRoadWater,
+    StrengthButton,
+    Sand,
+    Seaweed,
+    Ice,
+    ThinIce,
+    CrackedIce,
+    HotSprings,
+    RockStairs,
+    SandCave,
+    ImpassableEast,
+    ImpassableWest,
+    ImpassableNorth,
+    ImpassableSouth,
+    ImpassableNortheast,
+    ImpassableNorthwest,
+    ImpassableSoutheast,
+    ImpassableSouthwest,
+    // ledges
+    JumpEast,
+    JumpWest,
+    JumpNorth,
+    JumpSouth,
+    // forced movement
+    WalkEast,
+    WalkWest,
+    WalkNorth,
+    WalkSouth,
+    SlideEast,
+    SlideWest,
+    SlideNorth,
+    SlideSouth,
+    TrickHousePuzzle8Floor,
+    // water currents
+    EastwardCurrent,
+    WestwardCurrent,
+    NorthwardCurrent,
+    SouthwardCurrent,
+    // spinner tiles (Rocket Hideout)
+    SpinRight,
+    SpinLeft,
+    SpinUp,
+    SpinDown,
+    StopSpinning,
+    // warps
+    CaveDoor,
+    Ladder,
+    EastArrowWarp,
+    WestArrowWarp,
+    NorthArrowWarp,
+    SouthArrowWarp,
+    FallWarp,
+    RegularWarp,
+    Lavaridge1FWarp,
+    WarpDoor,
+    UpEscalator,
+    DownEscalator,
+    UpRightStairWarp,
+    UpLeftStairWarp,
+    DownRightStairWarp,
+    DownLeftStairWarp,
+    UnionRoomWarp,
+    // interactable (A button)
+    Counter,
+    Bookshelf,
+    PokemartShelf,
+    Pc,
+    Signpost,
+    RegionMap,
+    Television,
+    PokemonCenterSign,
+    PokemartSign,
+    Cabinet,
+    Kitchen,
+    Dresser,
+    Snacks,
+    CableClubWirelessMonitor,
+    BattleRecords,
+    Questionnaire,
+    Food,
+    IndigoPlateauSign1,
+    IndigoPlateauSign2,
+    Blueprints,
+    Painting,
+    PowerPlantMachine,
+    Telephone,
+    Computer,
+    AdvertisingPoster,
+    FoodSmellsTasty,
+    TrashBin,
+    Cup,
+    Porthole,
+    Window,
+    BlinkingLights,
+    NeatlyLinedUpTools,
+    ImpressiveMachine,
+    VideoGame,
+    Burglary,
+    TrainerTowerMonitor,
+    // Cycling Road slope
+    CyclingRoadPullDown,
+    CyclingRoadPullDownGrass,
+    Unknown(u16),
+}
+
+impl MetatileBehavior {
+    pub fn new(value: u16) -> MetatileBehavior {
+        match value {
+            0x00 => MetatileBehavior::Normal,
+            0x02 => MetatileBehavior::TallGrass,
+            0x08 => MetatileBehavior::Cave,
+            0x0a => MetatileBehavior::RunningDisallowed,
+            0x0b => MetatileBehavior::IndoorEncounter,
+            0x0c => MetatileBehavior::MountainTop,
+            0x10 => MetatileBehavior::PondWater,
+            0x11 => MetatileBehavior::FastWater,
+            0x12 => MetatileBehavior::DeepWater,
+            0x13 => MetatileBehavior::Waterfall,
+            0x15 => MetatileBehavior::OceanWater,
+            0x16 => MetatileBehavior::Puddle,
+            0x17 => MetatileBehavior::ShallowWater,
+            0x19 => MetatileBehavior::UnderwaterBlockedAbove,
+            0x1a => MetatileBehavior::UnusedWater,
+            0x1b => MetatileBehavior::CyclingRoadWater,
+            0x20 => MetatileBehavior::StrengthButton,
+            0x21 => MetatileBehavior::Sand,
+            0x22 => MetatileBehavior::Seaweed,
+            0x23 => MetatileBehavior::Ice,
+            0x26 => MetatileBehavior::ThinIce,
+            0x27 => MetatileBehavior::CrackedIce,
+            0x28 => MetatileBehavior::HotSprings,
+            0x2a => MetatileBehavior::RockStairs,
+            0x2b => MetatileBehavior::SandCave,
+            0x30 => MetatileBehavior::ImpassableEast,
+            0x31 => MetatileBehavior::ImpassableWest,
+            0x32 => MetatileBehavior::ImpassableNorth,
+            0x33 => MetatileBehavior::ImpassableSouth,
+            0x34 => MetatileBehavior::ImpassableNortheast,
+            0x35 => MetatileBehavior::ImpassableNorthwest,
+            0x36 => MetatileBehavior::ImpassableSoutheast,
+            0x37 => MetatileBehavior::ImpassableSouthwest,
+            0x38 => MetatileBehavior::JumpEast,
+            0x39 => MetatileBehavior::JumpWest,
+            0x3a => MetatileBehavior::JumpNorth,
+            0x3b => MetatileBehavior::JumpSouth,
+            0x40 => MetatileBehavior::WalkEast,
+            0x41 => MetatileBehavior::WalkWest,
+            0x42 => MetatileBehavior::WalkNorth,
+            0x43 => MetatileBehavior::WalkSouth,
+            0x44 => MetatileBehavior::SlideEast,
+            0x45 => MetatileBehavior::SlideWest,
+            0x46 => MetatileBehavior::SlideNorth,
+            0x47 => MetatileBehavior::SlideSouth,
+            0x48 => MetatileBehavior::TrickHousePuzzle8Floor,
+            0x50 => MetatileBehavior::EastwardCurrent,
+            0x51 => MetatileBehavior::WestwardCurrent,
+            0x52 => MetatileBehavior::NorthwardCurrent,
+            0x53 => MetatileBehavior::SouthwardCurrent,
+            0x54 => MetatileBehavior::SpinRight,
+            0x55 => MetatileBehavior::SpinLeft,
+            0x56 => MetatileBehavior::SpinUp,
+            0x57 => MetatileBehavior::SpinDown,
+            0x58 => MetatileBehavior::StopSpinning,
+            0x60 => MetatileBehavior::CaveDoor,
+            0x61 => MetatileBehavior::Ladder,
+            0x62 => MetatileBehavior::EastArrowWarp,
+            0x63 => MetatileBehavior::WestArrowWarp,
+            0x64 => MetatileBehavior::NorthArrowWarp,
+            0x65 => MetatileBehavior::SouthArrowWarp,
+            0x66 => MetatileBehavior::FallWarp,
+            0x67 => MetatileBehavior::RegularWarp,
+            0x68 => MetatileBehavior::Lavaridge1FWarp,
+            0x69 => MetatileBehavior::WarpDoor,
+            0x6a => MetatileBehavior::UpEscalator,
+            0x6b => MetatileBehavior::DownEscalator,
+            0x6c => MetatileBehavior::UpRightStairWarp,
+            0x6d => MetatileBehavior::UpLeftStairWarp,
+            0x6e => MetatileBehavior::DownRightStairWarp,
+            0x6f => MetatileBehavior::DownLeftStairWarp,
+            0x71 => MetatileBehavior::UnionRoomWarp,
+            0x80 => MetatileBehavior::Counter,
+            0x81 => MetatileBehavior::Bookshelf,
+            0x82 => MetatileBehavior::PokemartShelf,
+            0x83 => MetatileBehavior::Pc,
+            0x84 => MetatileBehavior::Signpost,
+            0x85 => MetatileBehavior::RegionMap,
+            0x86 => MetatileBehavior::Television,
+            0x87 => MetatileBehavior::PokemonCenterSign,
+            0x88 => MetatileBehavior::PokemartSign,
+            0x89 => MetatileBehavior::Cabinet,
+            0x8a => MetatileBehavior::Kitchen,
+            0x8b => MetatileBehavior::Dresser,
+            0x8c => MetatileBehavior::Snacks,
+            0x8d => MetatileBehavior::CableClubWirelessMonitor,
+            0x8e => MetatileBehavior::BattleRecords,
+            0x8f => MetatileBehavior::Questionnaire,
+            0x90 => MetatileBehavior::Food,
+            0x91 => MetatileBehavior::IndigoPlateauSign1,
+            0x92 => MetatileBehavior::IndigoPlateauSign2,
+            0x93 => MetatileBehavior::Blueprints,
+            0x94 => MetatileBehavior::Painting,
+            0x95 => MetatileBehavior::PowerPlantMachine,
+            0x96 => MetatileBehavior::Telephone,
+            0x97 => MetatileBehavior::Computer,
+            0x98 => MetatileBehavior::AdvertisingPoster,
+            0x99 => MetatileBehavior::FoodSmellsTasty,
+            0x9a => MetatileBehavior::TrashBin,
+            0x9b => MetatileBehavior::Cup,
+            0x9c => MetatileBehavior::Porthole,
+            0x9d => MetatileBehavior::Window,
+            0x9e => MetatileBehavior::BlinkingLights,
+            0x9f => MetatileBehavior::NeatlyLinedUpTools,
+            0xa0 => MetatileBehavior::ImpressiveMachine,
+            0xa1 => MetatileBehavior::VideoGame,
+            0xa2 => MetatileBehavior::Burglary,
+            0xa3 => MetatileBehavior::TrainerTowerMonitor,
+            0xd0 => MetatileBehavior::CyclingRoadPullDown,
+            0xd1 => MetatileBehavior::CyclingRoadPullDownGrass,
+            _ => MetatileBehavior::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u16 {
+        match self {
+            MetatileBehavior::Normal => 0x00,
+            MetatileBehavior::TallGrass => 0x02,
+            MetatileBehavior::Cave => 0x08,
+            MetatileBehavior::RunningDisallowed => 0x0a,
+            MetatileBehavior::IndoorEncounter => 0x0b,
+            MetatileBehavior::MountainTop => 0x0c,
+            MetatileBehavior::PondWater => 0x10,
+            MetatileBehavior::FastWater => 0x11,
+            MetatileBehavior::DeepWater => 0x12,
+            MetatileBehavior::Waterfall => 0x13,
+            MetatileBehavior::OceanWater => 0x15,
+            MetatileBehavior::Puddle => 0x16,
+            MetatileBehavior::ShallowWater => 0x17,
+            MetatileBehavior::UnderwaterBlockedAbove => 0x19,
+            MetatileBehavior::UnusedWater => 0x1a,
+            MetatileBehavior::CyclingRoadWater => 0x1b,
+            MetatileBehavior::StrengthButton => 0x20,
+            MetatileBehavior::Sand => 0x21,
+            MetatileBehavior::Seaweed => 0x22,
+            MetatileBehavior::Ice => 0x23,
+            MetatileBehavior::ThinIce => 0x26,
+            MetatileBehavior::CrackedIce => 0x27,
+            MetatileBehavior::HotSprings => 0x28,
+            MetatileBehavior::RockStairs => 0x2a,
+            MetatileBehavior::SandCave => 0x2b,
+            MetatileBehavior::ImpassableEast => 0x30,
+            MetatileBehavior::ImpassableWest => 0x31,
+            MetatileBehavior::ImpassableNorth => 0x32,
+            MetatileBehavior::ImpassableSouth => 0x33,
+            MetatileBehavior::ImpassableNortheast => 0x34,
+            MetatileBehavior::ImpassableNorthwest => 0x35,
+            MetatileBehavior::ImpassableSoutheast => 0x36,
+            MetatileBehavior::ImpassableSouthwest => 0x37,
+            MetatileBehavior::JumpEast => 0x38,
+            MetatileBehavior::JumpWest => 0x39,
+            MetatileBehavior::JumpNorth => 0x3a,
+            MetatileBehavior::JumpSouth => 0x3b,
+            MetatileBehavior::WalkEast => 0x40,
+            MetatileBehavior::WalkWest => 0x41,
+            MetatileBehavior::WalkNorth => 0x42,
+            MetatileBehavior::WalkSouth => 0x43,
+            MetatileBehavior::SlideEast => 0x44,
+            MetatileBehavior::SlideWest => 0x45,
+            MetatileBehavior::SlideNorth => 0x46,
+            MetatileBehavior::SlideSouth => 0x47,
+            MetatileBehavior::TrickHousePuzzle8Floor => 0x48,
+            MetatileBehavior::EastwardCurrent => 0x50,
+            MetatileBehavior::WestwardCurrent => 0x51,
+            MetatileBehavior::NorthwardCurrent => 0x52,
+            MetatileBehavior::SouthwardCurrent => 0x53,
+            MetatileBehavior::SpinRight => 0x54,
+            MetatileBehavior::SpinLeft => 0x55,
+            MetatileBehavior::SpinUp => 0x56,
+            MetatileBehavior::SpinDown => 0x57,
+            MetatileBehavior::StopSpinning => 0x58,
+            MetatileBehavior::CaveDoor => 0x60,
+            MetatileBehavior::Ladder => 0x61,
+            MetatileBehavior::EastArrowWarp => 0x62,
+            MetatileBehavior::WestArrowWarp => 0x63,
+            MetatileBehavior::NorthArrowWarp => 0x64,
+            MetatileBehavior::SouthArrowWarp => 0x65,
+            MetatileBehavior::FallWarp => 0x66,
+            MetatileBehavior::RegularWarp => 0x67,
+            MetatileBehavior::Lavaridge1FWarp => 0x68,
+            MetatileBehavior::WarpDoor => 0x69,
+            MetatileBehavior::UpEscalator => 0x6a,
+            MetatileBehavior::DownEscalator => 0x6b,
+            MetatileBehavior::UpRightStairWarp => 0x6c,
+            MetatileBehavior::UpLeftStairWarp => 0x6d,
+            MetatileBehavior::DownRightStairWarp => 0x6e,
+            MetatileBehavior::DownLeftStairWarp => 0x6f,
+            MetatileBehavior::UnionRoomWarp => 0x71,
+            MetatileBehavior::Counter => 0x80,
+            MetatileBehavior::Bookshelf => 0x81,
+            MetatileBehavior::PokemartShelf => 0x82,
+            MetatileBehavior::Pc => 0x83,
+            MetatileBehavior::Signpost => 0x84,
+            MetatileBehavior::RegionMap => 0x85,
+            MetatileBehavior::Television => 0x86,
+            MetatileBehavior::PokemonCenterSign => 0x87,
+            MetatileBehavior::PokemartSign => 0x88,
+            MetatileBehavior::Cabinet => 0x89,
+            MetatileBehavior::Kitchen => 0x8a,
+            MetatileBehavior::Dresser => 0x8b,
+            MetatileBehavior::Snacks => 0x8c,
+            MetatileBehavior::CableClubWirelessMonitor => 0x8d,
+            MetatileBehavior::BattleRecords => 0x8e,
+            MetatileBehavior::Questionnaire => 0x8f,
+            MetatileBehavior::Food => 0x90,
+            MetatileBehavior::IndigoPlateauSign1 => 0x91,
+            MetatileBehavior::IndigoPlateauSign2 => 0x92,
+            MetatileBehavior::Blueprints => 0x93,
+            MetatileBehavior::Painting => 0x94,
+            MetatileBehavior::PowerPlantMachine => 0x95,
+            MetatileBehavior::Telephone => 0x96,
+            MetatileBehavior::Computer => 0x97,
+            MetatileBehavior::AdvertisingPoster => 0x98,
+            MetatileBehavior::FoodSmellsTasty => 0x99,
+            MetatileBehavior::TrashBin => 0x9a,
+            MetatileBehavior::Cup => 0x9b,
+            MetatileBehavior::Porthole => 0x9c,
+            MetatileBehavior::Window => 0x9d,
+            MetatileBehavior::BlinkingLights => 0x9e,
+            MetatileBehavior::NeatlyLinedUpTools => 0x9f,
+            MetatileBehavior::ImpressiveMachine => 0xa0,
+            MetatileBehavior::VideoGame => 0xa1,
+            MetatileBehavior::Burglary => 0xa2,
+            MetatileBehavior::TrainerTowerMonitor => 0xa3,
+            MetatileBehavior::CyclingRoadPullDown => 0xd0,
+            MetatileBehavior::CyclingRoadPullDownGrass => 0xd1,
+            MetatileBehavior::Unknown(value) => *value,
+        }
+    }
+}
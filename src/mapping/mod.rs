@@ -2,17 +2,37 @@ mod map_header;
 pub use self::map_header::MapHeader;
 
 mod map_layout;
-pub use self::map_layout::MapLayout;
+pub use self::map_layout::{Anchor, MapLayout};
 
 mod map_tileset;
 pub use self::map_tileset::MapTileset;
 
+mod tileset_animation;
+pub use self::tileset_animation::TilesetAnimation;
+
 mod map_block;
 pub use self::map_block::MapBlock;
 
+mod map_edit_buffer;
+pub use self::map_edit_buffer::{FillMode, MapEditBuffer, MapRegion};
+
+mod object_event;
+pub use self::object_event::ObjectEvent;
+
+mod object_event_graphics_info;
+pub use self::object_event_graphics_info::AnimCommand;
+pub use self::object_event_graphics_info::ObjectEventGraphicsInfo;
+
 mod block;
+pub use self::block::Background;
 pub use self::block::Block;
+pub use self::block::BlockEncounter;
+pub use self::block::BlockWarning;
 pub use self::block::InvalidBlock;
+pub use self::block::Terrain;
+
+mod metatile_behavior;
+pub use self::metatile_behavior::MetatileBehavior;
 
 mod tile;
-pub use self::tile::Tile;
+pub use self::tile::{InvalidTile, Tile};
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::mapping::*;
+use crate::rom::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct ObjectEvent {
+    pub local_id: u8,
+    pub graphics_id: u8,
+    pub kind: u8,
+    pub x: i16,
+    pub y: i16,
+    pub elevation: u8,
+    pub movement_type: u8,
+    pub movement_range_x: u8, // 0 - 3
+    pub movement_range_y: u8, // 4 - 7
+    pub trainer_type: u16,
+    pub trainer_range: u16, // berry tree id for berry trees
+    pub script_addr: usize,
+    pub flag_id: u16,
+}
+
+impl ObjectEvent {
+    pub fn read(address: usize, rom: &mut Rom) -> Result<ObjectEvent> {
+        rom.seek_to(address)?;
+        let local_id = rom.read_u8()?;
+        let graphics_id = rom.read_u8()?;
+        let kind = rom.read_u8()?;
+        let _padding = rom.read_u8()?;
+        let x = rom.read_u16()? as i16;
+        let y = rom.read_u16()? as i16;
+        let elevation = rom.read_u8()?;
+        let movement_type = rom.read_u8()?;
+        let movement_range = rom.read_u16()?;
+        Ok(ObjectEvent {
+            local_id,
+            graphics_id,
+            kind,
+            x,
+            y,
+            elevation,
+            movement_type,
+            movement_range_x: (movement_range & 0xF) as u8,
+            movement_range_y: ((movement_range >> 4) & 0xF) as u8,
+            trainer_type: rom.read_u16()?,
+            trainer_range: rom.read_u16()?,
+            script_addr: rom.read_address()?,
+            flag_id: rom.read_u16()?,
+        })
+    }
+    pub fn get_graphics_info(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<ObjectEventGraphicsInfo> {
+        ObjectEventGraphicsInfo::get(rom, self.graphics_id)
+    }
+}
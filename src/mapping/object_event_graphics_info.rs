@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use crate::error::*;
+use crate::graphics::*;
+use crate::rom::*;
+
+const GRAPHICS_INFO_POINTERS_ADDRESS: usize = 0x39FDB0;
+const SPRITE_PALETTES_ADDRESS: usize = 0x3A5158;
+const NUM_OBJECT_EVENT_GRAPHICS: usize = 152;
+const MAX_NUM_FRAMES: usize = 0x40;
+const PALETTE_TAG_NONE: u16 = 0x11FF;
+
+#[derive(Clone, Copy, Debug)]
+pub enum AnimCommand {
+    Frame {
+        image_value: u16,
+        duration: u8,
+        h_flip: bool,
+        v_flip: bool,
+    },
+    Loop(u8),
+    Jump(u8),
+    End,
+}
+
+pub struct ObjectEventGraphicsInfo {
+    pub graphics_id: u8,
+    pub tile_tag: u16,
+    pub palette_tag: u16,
+    pub reflection_palette_tag: u16,
+    pub size: u16,
+    pub width: i16,
+    pub height: i16,
+    pub palette_slot: u8,                      // 0 - 3
+    pub shadow_size: u8,                       // 4 - 5
+    pub inanimate: bool,                       // 6
+    pub disable_reflection_palette_load: bool, // 7
+    pub tracks: u8,
+    pub oam_addr: usize,
+    pub subsprite_tables_addr: usize,
+    pub anims_addr: usize,
+    pub images_addr: usize,
+    pub affine_anims_addr: usize,
+}
+
+impl ObjectEventGraphicsInfo {
+    pub fn get(
+        rom: &mut Rom,
+        graphics_id: u8,
+    ) -> Result<ObjectEventGraphicsInfo> {
+        rom.seek_to(GRAPHICS_INFO_POINTERS_ADDRESS + graphics_id as usize * 4)?;
+        let address = rom.read_address()?;
+        ObjectEventGraphicsInfo::read(address, rom, graphics_id)
+    }
+    pub fn read(
+        address: usize,
+        rom: &mut Rom,
+        graphics_id: u8,
+    ) -> Result<ObjectEventGraphicsInfo> {
+        rom.seek_to(address)?;
+        let tile_tag = rom.read_u16()?;
+        let palette_tag = rom.read_u16()?;
+        let reflection_palette_tag = rom.read_u16()?;
+        let size = rom.read_u16()?;
+        let width = rom.read_u16()? as i16;
+        let height = rom.read_u16()? as i16;
+        let flags = rom.read_u8()?;
+        let tracks = rom.read_u8()?;
+        let _padding = rom.read_u16()?;
+        Ok(ObjectEventGraphicsInfo {
+            graphics_id,
+            tile_tag,
+            palette_tag,
+            reflection_palette_tag,
+            size,
+            width,
+            height,
+            palette_slot: flags & 0xF,
+            shadow_size: (flags >> 4) & 3,
+            inanimate: (flags >> 6) & 1 != 0,
+            disable_reflection_palette_load: (flags >> 7) != 0,
+            tracks,
+            oam_addr: rom.read_address()?,
+            subsprite_tables_addr: rom.read_address()?,
+            anims_addr: rom.read_address()?,
+            images_addr: rom.read_address()?,
+            affine_anims_addr: rom.read_address()?,
+        })
+    }
+    pub fn get_palette(&self, rom: &mut Rom) -> Result<Vec<Color>> {
+        let mut address = SPRITE_PALETTES_ADDRESS;
+        loop {
+            rom.seek_to(address)?;
+            let palette_addr = rom.read_address()?;
+            let tag = rom.read_u16()?;
+            if palette_addr == 0 || tag == PALETTE_TAG_NONE {
+                // unknown tags fall back to a greyscale palette
+                return Ok((0..16).map(|i| Color::new(i * 0x842)).collect());
+            }
+            if tag == self.palette_tag {
+                rom.seek_to(palette_addr)?;
+                let mut palette = vec![];
+                for _ in 0..16 {
+                    palette.push(Color::new(rom.read_u16()?));
+                }
+                return Ok(palette);
+            }
+            address += 8;
+        }
+    }
+    // the number of frames isn't stored anywhere, so frames are read as long as
+    // they have the expected size, follow each other in the sprite sheet and
+    // don't belong to the frame table of another graphics info
+    pub fn get_frame_addresses(&self, rom: &mut Rom) -> Result<Vec<usize>> {
+        let mut other_images_addrs = HashSet::new();
+        for graphics_id in 0..NUM_OBJECT_EVENT_GRAPHICS {
+            if graphics_id == self.graphics_id as usize {
+                continue;
+            }
+            rom.seek_to(GRAPHICS_INFO_POINTERS_ADDRESS + graphics_id * 4)?;
+            let address = rom.read_address()?;
+            rom.seek_to(address + 0x1C)?;
+            other_images_addrs.insert(rom.read_address()?);
+        }
+
+        let mut frame_addrs: Vec<usize> = vec![];
+        for frame_num in 0..MAX_NUM_FRAMES {
+            let entry_addr = self.images_addr + frame_num * 8;
+            if frame_num > 0 && other_images_addrs.contains(&entry_addr) {
+                break;
+            }
+            rom.seek_to(entry_addr)?;
+            let data_addr = match rom.read_address() {
+                Ok(data_addr) => data_addr,
+                Err(_) => break,
+            };
+            let size = rom.read_u16()?;
+            if data_addr == 0 || size != self.size {
+                break;
+            }
+            if let Some(prev_addr) = frame_addrs.last() {
+                if *prev_addr + self.size as usize != data_addr {
+                    break;
+                }
+            }
+            frame_addrs.push(data_addr);
+        }
+        Ok(frame_addrs)
+    }
+    pub fn get_frames(&self, rom: &mut Rom) -> Result<Vec<Image>> {
+        let palette = self.get_palette(rom)?;
+        let mut frames = vec![];
+        for frame_addr in self.get_frame_addresses(rom)? {
+            rom.seek_to(frame_addr)?;
+            let data = rom.read_data(self.size as usize);
+            frames.push(Image::from_tiles(
+                &data,
+                self.width as usize,
+                self.height as usize,
+                palette.clone(),
+            ));
+        }
+        Ok(frames)
+    }
+    pub fn get_frames_as_png(&self, rom: &mut Rom) -> Result<Vec<String>> {
+        Ok(self
+            .get_frames(rom)?
+            .iter()
+            .map(|frame| frame.get_as_png())
+            .collect())
+    }
+    pub fn get_anim(
+        &self,
+        rom: &mut Rom,
+        anim_num: usize,
+    ) -> Result<Vec<AnimCommand>> {
+        rom.seek_to(self.anims_addr + anim_num * 4)?;
+        rom.seek_to_address_read()?;
+        let mut commands = vec![];
+        loop {
+            let value = rom.read_u32()?;
+            let command = match (value & 0xFFFF) as i16 {
+                -1 => AnimCommand::End,
+                -2 => AnimCommand::Jump(((value >> 16) & 0x3F) as u8),
+                -3 => AnimCommand::Loop(((value >> 16) & 0x3F) as u8),
+                _ => AnimCommand::Frame {
+                    image_value: (value & 0xFFFF) as u16,
+                    duration: ((value >> 16) & 0x3F) as u8,
+                    h_flip: (value >> 22) & 1 != 0,
+                    v_flip: (value >> 23) & 1 != 0,
+                },
+            };
+            commands.push(command);
+            if matches!(command, AnimCommand::End | AnimCommand::Jump(_)) {
+                return Ok(commands);
+            }
+        }
+    }
+}
@@ -24,12 +24,20 @@ pub struct Tile {
 impl Tile {
     pub fn read(address: usize, rom: &mut Rom) -> Result<Tile> {
         rom.seek_to(address)?;
-        let value = rom.read_u16()?;
-        Ok(Tile {
+        Ok(Tile::new(rom.read_u16()?))
+    }
+    pub fn new(value: u16) -> Tile {
+        Tile {
             tile_id: value & 0x3FF,
             h_flip: ((value >> 0xA) & 1) != 0,
             v_flip: ((value >> 0xB) & 1) != 0,
             palette_id: (value >> 0xC) as u8,
-        })
+        }
+    }
+    pub fn value(&self) -> u16 {
+        (self.tile_id & 0x3FF)
+            | (self.h_flip as u16) << 0xA
+            | (self.v_flip as u16) << 0xB
+            | (self.palette_id as u16 & 0xF) << 0xC
     }
 }
@@ -0,0 +1,267 @@
+use std::collections::HashSet;
+
+use crate::constants::*;
+use crate::error::*;
+use crate::rom::*;
+
+const VRAM_ADDRESS: usize = 0x6000000;
+const SIZE_TILE_DATA: usize = SIZE_TILE * SIZE_TILE / 2;
+const MAX_FUNCTION_LENGTH: usize = 0x200;
+const MAX_NUM_ANIMATION_FRAMES: usize = 0x20;
+const MAX_NUM_PREVIEW_FRAMES: usize = 0x200;
+// most FRLG animations advance every 16 calls of the tileset callback, so
+// this is used for animations that aren't known
+const DEFAULT_FRAME_DURATION: usize = 16;
+
+// the FRLG animations by their first tile: their names, and their frame
+// durations (the `N` of the `timer % N` of their tileset callback)
+const KNOWN_ANIMATIONS: [(usize, &str, usize); 8] = [
+    (416, "water", 16),
+    (464, "sand_waters_edge", 16),
+    (508, "flower", 16),
+    (739, "celadon_gym_flowers", 16),
+    (744, "celadon_city_fountain", 12),
+    (880, "vermilion_gym_motorized_door", 2),
+    (896, "mt_ember_steam", 16),
+    (976, "silph_co_fountain", 10),
+];
+
+#[derive(Clone, Debug)]
+pub struct TilesetAnimation {
+    pub name: String,
+    pub start_tile: usize,
+    pub num_tiles: usize,
+    pub frame_addrs: Vec<usize>, // in playback order, may repeat
+    pub frame_duration: usize,   // in game frames (1/60 s)
+}
+
+impl TilesetAnimation {
+    // the animations of a tileset aren't described by any table, only by the
+    // code of its callback: the callback installs an animation function, which
+    // calls one function per animation, each copying a frame from a frame table
+    // into VRAM; the frame tables and VRAM destinations are read from the
+    // literal pools of those functions
+    pub fn read(
+        tileset_callback_addr: usize,
+        rom: &mut Rom,
+    ) -> Result<Vec<TilesetAnimation>> {
+        if tileset_callback_addr == 0 {
+            return Ok(vec![]);
+        }
+        let (literals, _) = scan_function(tileset_callback_addr & !1, rom)?;
+        let anim_fn_addr = match literals
+            .iter()
+            .filter_map(|value| to_rom_address(*value))
+            .find(|address| address & 1 == 1)
+        {
+            Some(address) => address & !1,
+            None => return Ok(vec![]),
+        };
+        let (_, calls) = scan_function(anim_fn_addr, rom)?;
+
+        let mut sources = vec![];
+        for fn_addr in [&[anim_fn_addr][..], &calls].concat() {
+            let (literals, _) = scan_function(fn_addr, rom)?;
+            let tables = literals
+                .iter()
+                .filter_map(|value| to_rom_address(*value))
+                .filter(|address| address & 3 == 0);
+            let destinations = literals
+                .iter()
+                .map(|value| *value as usize)
+                .filter(|value| {
+                    (VRAM_ADDRESS..VRAM_ADDRESS + 0x10000).contains(value)
+                });
+            sources.extend(tables.zip(destinations));
+        }
+
+        let table_addrs = sources
+            .iter()
+            .map(|(table_addr, _)| *table_addr)
+            .collect::<HashSet<usize>>();
+        let mut animations = vec![];
+        for (table_addr, destination) in sources {
+            let frame_addrs = read_frame_table(table_addr, &table_addrs, rom)?;
+            let num_tiles = match frame_addrs
+                .iter()
+                .find(|a| **a > frame_addrs[0])
+            {
+                Some(address) => (address - frame_addrs[0]) / SIZE_TILE_DATA,
+                None => continue,
+            };
+            let start_tile = (destination - VRAM_ADDRESS) / SIZE_TILE_DATA;
+            let (name, frame_duration) = match KNOWN_ANIMATIONS
+                .iter()
+                .find(|(tile, _, _)| *tile == start_tile)
+            {
+                Some((_, name, duration)) => (name.to_string(), *duration),
+                None => (
+                    format!("animation_{}", start_tile),
+                    DEFAULT_FRAME_DURATION,
+                ),
+            };
+            animations.push(TilesetAnimation {
+                name,
+                start_tile,
+                num_tiles,
+                frame_addrs,
+                frame_duration,
+            });
+        }
+        Ok(animations)
+    }
+    pub fn get_cycle_length(&self) -> usize {
+        self.frame_addrs.len() * self.frame_duration
+    }
+    pub fn get_frame_tiles(
+        &self,
+        rom: &mut Rom,
+        frame_num: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        rom.seek_to(self.frame_addrs[frame_num])?;
+        let data = rom.read_data(self.num_tiles * SIZE_TILE_DATA);
+        Ok(data.chunks(SIZE_TILE_DATA).map(|x| x.to_vec()).collect())
+    }
+    // replaces the animated tiles by the frame shown `tick` game frames in
+    pub fn apply_at(
+        &self,
+        rom: &mut Rom,
+        tick: usize,
+        tiles_data: &mut Vec<Vec<u8>>,
+    ) -> Result<()> {
+        let frame_num = (tick / self.frame_duration) % self.frame_addrs.len();
+        let frame_tiles = self.get_frame_tiles(rom, frame_num)?;
+        let end_tile = self.start_tile + frame_tiles.len();
+        if tiles_data.len() < end_tile {
+            tiles_data.resize(end_tile, vec![0; SIZE_TILE_DATA]);
+        }
+        tiles_data.splice(self.start_tile..end_tile, frame_tiles);
+        Ok(())
+    }
+    // the ticks at which at least one animation changes frame during a full
+    // cycle of all animations, along with how long each step lasts
+    pub fn get_steps(animations: &[TilesetAnimation]) -> Vec<(usize, usize)> {
+        let period = animations
+            .iter()
+            .map(|animation| animation.get_cycle_length())
+            .fold(1, lcm);
+        let step = animations
+            .iter()
+            .map(|animation| animation.frame_duration)
+            .fold(0, gcd)
+            .max(1);
+        (0..period)
+            .step_by(step)
+            .take(MAX_NUM_PREVIEW_FRAMES)
+            .map(|tick| (tick, step))
+            .collect()
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+fn to_rom_address(value: u32) -> Option<usize> {
+    match value {
+        0x8000000..=0x9FFFFFF => Some((value - 0x8000000) as usize),
+        _ => None,
+    }
+}
+
+// frame tables aren't terminated: entries are read as long as they point to
+// frames of the same size following the first one, and the table doesn't run
+// into the table of another animation
+fn read_frame_table(
+    table_addr: usize,
+    table_addrs: &HashSet<usize>,
+    rom: &mut Rom,
+) -> Result<Vec<usize>> {
+    let mut frame_addrs: Vec<usize> = vec![];
+    for i in 0..MAX_NUM_ANIMATION_FRAMES {
+        let entry_addr = table_addr + i * 4;
+        if i > 0 && table_addrs.contains(&entry_addr) {
+            break;
+        }
+        rom.seek_to(entry_addr)?;
+        let address = match rom.read_address() {
+            Ok(address) if address != 0 => address,
+            _ => break,
+        };
+        if let Some(first) = frame_addrs.first() {
+            let size = match frame_addrs.iter().find(|a| *a > first) {
+                Some(second) => second - first,
+                None => address.saturating_sub(*first),
+            };
+            if address < *first
+                || size == 0
+                || (address - first) % size != 0
+                || (address - first) / size >= MAX_NUM_ANIMATION_FRAMES
+            {
+                break;
+            }
+        }
+        frame_addrs.push(address);
+    }
+    Ok(frame_addrs)
+}
+
+// walks the THUMB code of a function until it returns, collecting the values
+// loaded from its literal pool and the targets of its calls
+fn scan_function(
+    address: usize,
+    rom: &mut Rom,
+) -> Result<(Vec<u32>, Vec<usize>)> {
+    let mut literals = vec![];
+    let mut calls = vec![];
+    let mut furthest_branch = address;
+    let mut pos = address;
+    while pos < address + MAX_FUNCTION_LENGTH {
+        rom.seek_to(pos)?;
+        let instr = rom.read_u16()?;
+        match instr {
+            // ldr rd, [pc, #imm]
+            0x4800..=0x4FFF => {
+                let literal_addr =
+                    ((pos + 4) & !3) + (instr as usize & 0xFF) * 4;
+                rom.seek_to(literal_addr)?;
+                literals.push(rom.read_u32()?);
+            }
+            // bl (two halves)
+            0xF000..=0xF7FF => {
+                let low = rom.read_u16()?;
+                if low & 0xF800 == 0xF800 {
+                    let offset = ((instr as i32 & 0x7FF) << 21 >> 9)
+                        | ((low as i32 & 0x7FF) << 1);
+                    calls.push((pos as i32 + 4 + offset) as usize);
+                    pos += 2;
+                }
+            }
+            // b<cond> / b
+            0xD000..=0xDDFF => {
+                let offset = (instr as i8 as i32) * 2;
+                let target = (pos as i32 + 4 + offset) as usize;
+                furthest_branch = furthest_branch.max(target);
+            }
+            0xE000..=0xE7FF => {
+                let offset = ((instr as i32 & 0x7FF) << 21) >> 20;
+                let target = (pos as i32 + 4 + offset) as usize;
+                furthest_branch = furthest_branch.max(target);
+            }
+            // pop {..., pc} / bx rm
+            0xBD00..=0xBDFF | 0x4700..=0x477F if pos >= furthest_branch => {
+                break;
+            }
+            _ => {}
+        }
+        pos += 2;
+    }
+    Ok((literals, calls))
+}
@@ -0,0 +1,4 @@
+pub const NUM_MOVES: usize = 355;
+
+pub const SIZE_MOVE: usize = 12;
+pub const SIZE_MOVE_NAME: usize = 13;
@@ -0,0 +1,4 @@
+pub mod constants;
+
+mod move_data;
+pub use self::move_data::*;
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::moves::constants::*;
+use crate::rom::*;
+use crate::species::PokemonType;
+use crate::text::*;
+
+const BATTLE_MOVES_ADDRESS: usize = 0x250C04;
+const MOVE_NAMES_ADDRESS: usize = 0x247094;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct MoveFlags {
+    pub makes_contact: bool,           // 0
+    pub affected_by_protect: bool,     // 1
+    pub affected_by_magic_coat: bool,  // 2
+    pub affected_by_snatch: bool,      // 3
+    pub affected_by_mirror_move: bool, // 4
+    pub affected_by_kings_rock: bool,  // 5
+    #[serde(default)]
+    pub unused_bits: u8, // 6 - 7, kept as is, some hacks use them
+}
+
+const UNUSED_BITS_MASK: u8 = 0xC0;
+
+impl MoveFlags {
+    pub fn new(value: u8) -> MoveFlags {
+        MoveFlags {
+            makes_contact: value & 0x01 != 0,
+            affected_by_protect: value & 0x02 != 0,
+            affected_by_magic_coat: value & 0x04 != 0,
+            affected_by_snatch: value & 0x08 != 0,
+            affected_by_mirror_move: value & 0x10 != 0,
+            affected_by_kings_rock: value & 0x20 != 0,
+            unused_bits: value & UNUSED_BITS_MASK,
+        }
+    }
+    pub fn value(&self) -> u8 {
+        self.makes_contact as u8
+            | (self.affected_by_protect as u8) << 1
+            | (self.affected_by_magic_coat as u8) << 2
+            | (self.affected_by_snatch as u8) << 3
+            | (self.affected_by_mirror_move as u8) << 4
+            | (self.affected_by_kings_rock as u8) << 5
+            | self.unused_bits & UNUSED_BITS_MASK
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Move {
+    pub move_num: u16,
+    pub name: String,
+    pub effect: u8,
+    pub power: u8,
+    pub move_type: PokemonType,
+    pub accuracy: u8,
+    pub pp: u8,
+    pub effect_chance: u8,
+    pub target: u8,
+    pub priority: i8,
+    pub flags: MoveFlags,
+}
+
+impl Move {
+    pub fn get(rom: &mut Rom, move_num: u16) -> Result<Move> {
+        let name = Move::get_name(rom, move_num)?;
+        rom.seek_to(BATTLE_MOVES_ADDRESS + move_num as usize * SIZE_MOVE)?;
+        Ok(Move {
+            move_num,
+            name,
+            effect: rom.read_u8()?,
+            power: rom.read_u8()?,
+            move_type: PokemonType::new(rom.read_u8()?),
+            accuracy: rom.read_u8()?,
+            pp: rom.read_u8()?,
+            effect_chance: rom.read_u8()?,
+            target: rom.read_u8()?,
+            priority: rom.read_u8()? as i8,
+            flags: MoveFlags::new(rom.read_u8()?),
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Move>> {
+        let mut moves = vec![];
+        for move_num in 0..NUM_MOVES {
+            moves.push(Move::get(rom, move_num as u16)?);
+        }
+        Ok(moves)
+    }
+    pub fn get_name(rom: &mut Rom, move_num: u16) -> Result<String> {
+        read_text(
+            MOVE_NAMES_ADDRESS + move_num as usize * SIZE_MOVE_NAME,
+            rom,
+            SIZE_MOVE_NAME,
+        )
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        write_text(
+            MOVE_NAMES_ADDRESS + self.move_num as usize * SIZE_MOVE_NAME,
+            rom,
+            &self.name,
+            SIZE_MOVE_NAME,
+        )?;
+        rom.seek_to(BATTLE_MOVES_ADDRESS + self.move_num as usize * SIZE_MOVE)?;
+        rom.write_u8(self.effect)?;
+        rom.write_u8(self.power)?;
+        rom.write_u8(self.move_type.value())?;
+        rom.write_u8(self.accuracy)?;
+        rom.write_u8(self.pp)?;
+        rom.write_u8(self.effect_chance)?;
+        rom.write_u8(self.target)?;
+        rom.write_u8(self.priority as u8)?;
+        rom.write_u8(self.flags.value())
+    }
+    pub fn get_all_as_json(rom: &mut Rom) -> Result<String> {
+        Ok(serde_json::to_string(&Move::get_all(rom)?)?)
+    }
+    pub fn write_all_from_json(rom: &mut Rom, json: &str) -> Result<()> {
+        let moves: Vec<Move> = serde_json::from_str(json)?;
+        for battle_move in moves {
+            battle_move.write(rom)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_flags_round_trip() {
+        for value in 0..=u8::MAX {
+            assert_eq!(MoveFlags::new(value).value(), value);
+        }
+    }
+}
@@ -2,6 +2,19 @@ use crate::error::*;
 use crate::mapping::*;
 
 const MAP_BANK_TABLE_POINTER: usize = 0x05524C;
+const MAP_LAYOUT_TABLE_POINTER: usize = 0x055194;
+
+// vanilla FireRed data ends right before this address
+pub(crate) const FREE_SPACE_START: usize = 0x71A240;
+// free bytes kept before any allocation, so that data ending in 0xFF (e.g.
+// terminators) right before a free area is never mistaken for free space
+const FREE_SPACE_PADDING: usize = 0x10;
+
+// ends the pointer tables moved to free space (kept intact by the padding)
+const TABLE_TERMINATOR: u32 = 0xFFFFFFFF;
+
+// size of gMapLayouts, some of its layouts aren't used by any map
+const NUM_MAP_LAYOUTS_FR: usize = 0x16B;
 
 const BANK_SIZES_FR: [u8; 43] = [
     5, 123, 60, 66, 4, 6, 8, 10, 6, 8, 20, 10, 8, 2, 10, 4, 2, 2, 2, 1, 1, 2,
@@ -20,18 +33,25 @@ impl Rom {
     pub fn get_data(&self) -> Vec<u8> {
         self.data.to_vec()
     }
+    pub fn get_position(&self) -> usize {
+        self.pos
+    }
     pub fn seek_to(&mut self, address: usize) -> Result<()> {
-        return match address {
+        match address {
             0 => Err(Error::SeekToNullError),
-            0x8000000..=0xA000000 => Ok(self.pos = address - 0x8000000),
+            0x8000000..=0xA000000 => {
+                self.pos = address - 0x8000000;
+                Ok(())
+            }
             _ => {
-                return if address >= self.data.len() {
+                if address >= self.data.len() {
                     Err(Error::OutOfBoundsError(address))
                 } else {
-                    Ok(self.pos = address)
-                };
+                    self.pos = address;
+                    Ok(())
+                }
             }
-        };
+        }
     }
     pub fn seek_to_address_read(&mut self) -> Result<()> {
         let address = self.read_address()?;
@@ -77,7 +97,7 @@ impl Rom {
         }
     }
     pub fn read_data(&mut self, length: usize) -> Vec<u8> {
-        let data = (&self.data[self.pos..self.pos + length]).to_vec();
+        let data = self.data[self.pos..self.pos + length].to_vec();
         self.pos += length;
         data
     }
@@ -86,40 +106,159 @@ impl Rom {
             return Err(Error::OutOfBoundsError(self.pos));
         }
         self.data[self.pos] = value;
-        Ok(self.pos += 1)
+        self.pos += 1;
+        Ok(())
     }
     pub fn write_u16(&mut self, value: u16) -> Result<()> {
         if (self.pos + 1) > self.data.len() {
             return Err(Error::OutOfBoundsError(self.pos + 1));
         }
-        self.data[self.pos + 0] = (value & 0x00FF) as u8;
+        self.data[self.pos] = (value & 0x00FF) as u8;
         self.data[self.pos + 1] = ((value & 0xFF00) >> 8) as u8;
-        Ok(self.pos += 2)
+        self.pos += 2;
+        Ok(())
     }
     pub fn write_u32(&mut self, value: u32) -> Result<()> {
         if (self.pos + 3) > self.data.len() {
             return Err(Error::OutOfBoundsError(self.pos + 3));
         }
-        self.data[self.pos + 0] = (value & 0x000000FF) as u8;
+        self.data[self.pos] = (value & 0x000000FF) as u8;
         self.data[self.pos + 1] = ((value & 0x0000FF00) >> 8) as u8;
         self.data[self.pos + 2] = ((value & 0x00FF0000) >> 16) as u8;
         self.data[self.pos + 3] = ((value & 0xFF000000) >> 24) as u8;
-        Ok(self.pos += 4)
+        self.pos += 4;
+        Ok(())
+    }
+    pub fn write_data(&mut self, data: &[u8]) -> Result<()> {
+        if (self.pos + data.len()) > self.data.len() {
+            return Err(Error::OutOfBoundsError(self.pos + data.len()));
+        }
+        self.data[self.pos..self.pos + data.len()].copy_from_slice(data);
+        self.pos += data.len();
+        Ok(())
+    }
+    pub fn write_address(&mut self, address: usize) -> Result<()> {
+        match address {
+            0 => self.write_u32(0),
+            _ => self.write_u32((address | 0x8000000) as u32),
+        }
+    }
+    pub fn find_free_space(&self, length: usize) -> Result<usize> {
+        let mut run_start = FREE_SPACE_START;
+        for pos in FREE_SPACE_START..self.data.len() {
+            if self.data[pos] != 0xFF {
+                run_start = pos + 1;
+                continue;
+            }
+            let address = (run_start + FREE_SPACE_PADDING + 3) & !3;
+            if pos + 1 >= address + length {
+                return Ok(address);
+            }
+        }
+        Err(Error::NoFreeSpace(length))
+    }
+    // allocates a free area big enough for `data`, writes `data` into it and
+    // returns its address
+    pub fn allocate(&mut self, data: &[u8]) -> Result<usize> {
+        let address = self.find_free_space(data.len())?;
+        self.seek_to(address)?;
+        self.write_data(data)?;
+        Ok(address)
+    }
+    // writes `data` over the `old_length` bytes at `old_address` if it fits,
+    // otherwise allocates a new area; returns the data address
+    //
+    // vanilla data is often shared by several table entries (e.g. the Unown
+    // forms), so data that something else points to is never overwritten,
+    // and only data this tool allocated is freed
+    pub fn reallocate(
+        &mut self,
+        old_address: usize,
+        old_length: usize,
+        data: &[u8],
+    ) -> Result<usize> {
+        let is_shared =
+            old_address != 0 && self.count_pointers_to(old_address) > 1;
+        if old_address != 0 && !is_shared && data.len() <= old_length {
+            self.seek_to(old_address)?;
+            self.write_data(data)?;
+            return Ok(old_address);
+        }
+        if old_address != 0 && !is_shared && self.is_allocated(old_address) {
+            self.free(old_address, old_length)?;
+        }
+        self.allocate(data)
+    }
+    // whether `address` is past the vanilla data, where allocations happen
+    pub fn is_allocated(&self, address: usize) -> bool {
+        address >= FREE_SPACE_START
+    }
+    pub fn free(&mut self, address: usize, length: usize) -> Result<()> {
+        self.seek_to(address)?;
+        self.write_data(&vec![0xFF; length])
     }
     pub fn get_map_banks_names(&mut self) -> Result<Vec<Vec<String>>> {
         let mut banks = vec![];
-        //TODO: iterate through banks without hard-coded bank sizes
-        for (bank_num, bank_size) in BANK_SIZES_FR.iter().enumerate() {
+        for bank_num in 0..self.get_bank_count()? {
             let mut bank = vec![];
-            for map_num in 0..*bank_size {
+            for map_num in 0..self.get_bank_size(bank_num)? {
                 // bank.push(self.get_map_header(bank_num, map_num as usize).get_map_name(self));
-                self.seek_to_map(bank_num, map_num as usize)?;
+                self.seek_to_map(bank_num, map_num)?;
                 bank.push(format!("{:#x}", self.pos));
             }
-            banks.push(bank.try_into().unwrap());
+            banks.push(bank);
         }
         Ok(banks)
     }
+    pub fn get_bank_count(&mut self) -> Result<usize> {
+        let bank_table_addr = self.get_bank_table_address()?;
+        self.get_table_length(bank_table_addr, BANK_SIZES_FR.len())
+    }
+    pub fn get_bank_size(&mut self, bank_num: usize) -> Result<usize> {
+        let bank_addr = self.get_bank_address(bank_num)?;
+        let vanilla_size = BANK_SIZES_FR.get(bank_num).copied().unwrap_or(0);
+        self.get_table_length(bank_addr, vanilla_size as usize)
+    }
+    // adds a new map (see `MapHeader::allocate`) at the end of a bank, moving
+    // the bank's map table to free space; returns the new map's bank and map
+    // numbers
+    pub fn add_map(
+        &mut self,
+        bank_num: usize,
+        layout_id: u16,
+        map_section: u8,
+    ) -> Result<(usize, usize)> {
+        let bank_addr = self.get_bank_address(bank_num)?;
+        let bank_size = self.get_bank_size(bank_num)?;
+        let map_header_addr =
+            MapHeader::allocate(self, layout_id, map_section)?;
+        let new_bank_addr =
+            self.append_to_table(bank_addr, bank_size, map_header_addr)?;
+        // bank tables are only referenced by the table of banks
+        let bank_table_addr = self.get_bank_table_address()?;
+        self.seek_to(bank_table_addr + bank_num * 4)?;
+        self.write_address(new_bank_addr)?;
+        Ok((bank_num, bank_size))
+    }
+    // adds a new bank with a single new map in it (see `add_map`), moving the
+    // table of banks to free space; returns the new map's bank and map numbers
+    pub fn add_bank(
+        &mut self,
+        layout_id: u16,
+        map_section: u8,
+    ) -> Result<(usize, usize)> {
+        let bank_table_addr = self.get_bank_table_address()?;
+        let bank_count = self.get_bank_count()?;
+        let map_header_addr =
+            MapHeader::allocate(self, layout_id, map_section)?;
+        let bank_addr = self.append_to_table(0, 0, map_header_addr)?;
+        let new_bank_table_addr =
+            self.append_to_table(bank_table_addr, bank_count, bank_addr)?;
+        // the table of banks is only referenced by this pointer
+        self.seek_to(MAP_BANK_TABLE_POINTER)?;
+        self.write_address(new_bank_table_addr)?;
+        Ok((bank_count, 0))
+    }
     pub fn get_map_header(
         &mut self,
         bank_num: usize,
@@ -129,12 +268,360 @@ impl Rom {
         MapHeader::read(self.pos, self, bank_num, map_num)
     }
 
-    fn seek_to_map(&mut self, bank_num: usize, map_num: usize) -> Result<()> {
+    pub fn get_map_layout_table_address(&mut self) -> Result<usize> {
+        self.seek_to(MAP_LAYOUT_TABLE_POINTER)?;
+        self.read_address()
+    }
+    pub fn get_map_layout_count(&mut self) -> Result<usize> {
+        let table_addr = self.get_map_layout_table_address()?;
+        self.get_table_length(table_addr, NUM_MAP_LAYOUTS_FR)
+    }
+    // `layout_id` is 1-based, as in map headers
+    pub fn get_map_layout(&mut self, layout_id: u16) -> Result<MapLayout> {
+        let layout_addr = self.get_map_layout_address(layout_id)?;
+        MapLayout::read(layout_addr, self)
+    }
+    pub fn get_map_layout_address(&mut self, layout_id: u16) -> Result<usize> {
+        if layout_id == 0 || layout_id as usize > self.get_map_layout_count()? {
+            return Err(Error::InvalidLayoutId(layout_id));
+        }
+        let table_addr = self.get_map_layout_table_address()?;
+        self.seek_to(table_addr + (layout_id as usize - 1) * 4)?;
+        self.read_address()
+    }
+    // appends the layout at `layout_addr` to the layout table (moving the
+    // table to free space and updating the pointer to it) and returns its
+    // layout ID
+    pub fn register_map_layout(&mut self, layout_addr: usize) -> Result<u16> {
+        let table_addr = self.get_map_layout_table_address()?;
+        let layout_count = self.get_map_layout_count()?;
+        let new_table_addr =
+            self.append_to_table(table_addr, layout_count, layout_addr)?;
+        // the layout table is only referenced by this pointer
+        self.seek_to(MAP_LAYOUT_TABLE_POINTER)?;
+        self.write_address(new_table_addr)?;
+        Ok((layout_count + 1) as u16)
+    }
+
+    // number of (word-aligned) pointers to `address` in the whole ROM
+    fn count_pointers_to(&self, address: usize) -> usize {
+        let pointer = (address as u32 | 0x8000000).to_le_bytes();
+        self.data
+            .chunks_exact(4)
+            .filter(|&word| word == pointer)
+            .count()
+    }
+    // number of pointers in a table: the game doesn't store it, vanilla
+    // tables have hard-coded sizes and tables this tool moved to free space
+    // end with `TABLE_TERMINATOR`
+    //
+    // tables other tools moved to free space may have no terminator, so the
+    // scan stops at the first entry that isn't a pointer (or null) and then
+    // falls back to the vanilla length
+    fn get_table_length(
+        &mut self,
+        address: usize,
+        vanilla_length: usize,
+    ) -> Result<usize> {
+        if !self.is_allocated(address) {
+            return Ok(vanilla_length);
+        }
+        self.seek_to(address)?;
+        let mut length = 0;
+        while self.pos + 4 <= self.data.len() {
+            match self.read_u32()? {
+                TABLE_TERMINATOR => return Ok(length),
+                value if self.is_pointer_or_null(value) => length += 1,
+                _ => break,
+            }
+        }
+        Ok(length.min(vanilla_length))
+    }
+    // moves a table of `length` pointers to free space with a pointer to
+    // `address` appended (and the terminator); returns the new table address
+    // (a new table if `table_addr` is 0)
+    fn append_to_table(
+        &mut self,
+        table_addr: usize,
+        length: usize,
+        address: usize,
+    ) -> Result<usize> {
+        let mut old_length = length * 4;
+        let mut table = vec![];
+        if table_addr != 0 {
+            self.seek_to(table_addr)?;
+            table = self.read_data(old_length);
+            // the old terminator is freed along with the table, if there is
+            // one (it's never there in vanilla tables)
+            if self.is_allocated(table_addr)
+                && self.pos + 4 <= self.data.len()
+                && self.read_u32()? == TABLE_TERMINATOR
+            {
+                old_length += 4;
+            }
+        }
+        table.extend((address as u32 | 0x8000000).to_le_bytes());
+        table.extend(TABLE_TERMINATOR.to_le_bytes());
+        self.reallocate(table_addr, old_length, &table)
+    }
+    fn is_pointer_or_null(&self, value: u32) -> bool {
+        let address = value as usize;
+        address == 0
+            || (0x8000000..0x8000000 + self.data.len()).contains(&address)
+    }
+    fn get_bank_table_address(&mut self) -> Result<usize> {
         self.seek_to(MAP_BANK_TABLE_POINTER)?;
-        let bank_addr_pointer = self.read_address()? + bank_num * 4;
+        self.read_address()
+    }
+    fn get_bank_address(&mut self, bank_num: usize) -> Result<usize> {
+        if bank_num >= self.get_bank_count()? {
+            return Err(Error::OutOfBoundsError(bank_num));
+        }
+        let bank_addr_pointer = self.get_bank_table_address()? + bank_num * 4;
         self.seek_to(bank_addr_pointer)?;
-        let map_addr_pointer = self.read_address()? + map_num * 4;
+        self.read_address()
+    }
+    fn seek_to_map(&mut self, bank_num: usize, map_num: usize) -> Result<()> {
+        let map_addr_pointer = self.get_bank_address(bank_num)? + map_num * 4;
         self.seek_to(map_addr_pointer)?;
         self.seek_to_address_read()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FREE_SPACE_LENGTH: usize = 0x100;
+
+    // vanilla data (zeros) followed by `FREE_SPACE_LENGTH` bytes of free space
+    fn build_rom() -> Rom {
+        build_rom_with_free_space(FREE_SPACE_LENGTH)
+    }
+
+    fn build_rom_with_free_space(length: usize) -> Rom {
+        let mut data = vec![0; FREE_SPACE_START];
+        data.extend(vec![0xFF; length]);
+        Rom::new(data)
+    }
+
+    fn read_at(rom: &mut Rom, address: usize, length: usize) -> Vec<u8> {
+        rom.seek_to(address).unwrap();
+        rom.read_data(length)
+    }
+
+    fn write_pointer(rom: &mut Rom, at: usize, address: usize) {
+        rom.seek_to(at).unwrap();
+        rom.write_address(address).unwrap();
+    }
+
+    #[test]
+    fn allocate_keeps_padding() {
+        let mut rom = build_rom();
+        let first = rom.allocate(&[1; 6]).unwrap();
+        assert_eq!(first, FREE_SPACE_START + FREE_SPACE_PADDING);
+        let second = rom.allocate(&[2; 4]).unwrap();
+        assert_eq!(second, (first + 6 + FREE_SPACE_PADDING + 3) & !3);
+        assert_eq!(read_at(&mut rom, first, 6), [1; 6]);
+        assert_eq!(read_at(&mut rom, second, 4), [2; 4]);
+    }
+
+    #[test]
+    fn allocate_reuses_freed_space() {
+        let mut rom = build_rom();
+        let first = rom.allocate(&[1; 8]).unwrap();
+        let second = rom.allocate(&[2; 8]).unwrap();
+        rom.free(first, 8).unwrap();
+        assert_eq!(read_at(&mut rom, first, 8), [0xFF; 8]);
+        assert_eq!(rom.allocate(&[3; 8]).unwrap(), first);
+        assert_eq!(read_at(&mut rom, second, 8), [2; 8]);
+    }
+
+    #[test]
+    fn allocate_fails_without_free_space() {
+        let mut rom = build_rom();
+        let length = FREE_SPACE_LENGTH - FREE_SPACE_PADDING;
+        assert!(rom.find_free_space(length).is_ok());
+        assert!(matches!(
+            rom.allocate(&vec![0; length + 1]),
+            Err(Error::NoFreeSpace(l)) if l == length + 1
+        ));
+        let data = rom.get_data();
+        rom.allocate(&vec![0; length]).unwrap();
+        assert!(matches!(rom.allocate(&[0]), Err(Error::NoFreeSpace(1))));
+        assert_eq!(rom.get_data().len(), data.len());
+    }
+
+    #[test]
+    fn reallocate_writes_in_place_when_it_fits() {
+        let mut rom = build_rom();
+        let address = rom.allocate(&[1; 8]).unwrap();
+        write_pointer(&mut rom, 0x100, address);
+        assert_eq!(rom.reallocate(address, 8, &[2; 8]).unwrap(), address);
+        assert_eq!(read_at(&mut rom, address, 8), [2; 8]);
+        // vanilla data pointed to once is overwritten as well
+        write_pointer(&mut rom, 0x104, 0x200);
+        assert_eq!(rom.reallocate(0x200, 8, &[3; 4]).unwrap(), 0x200);
+        assert_eq!(read_at(&mut rom, 0x200, 8), [3, 3, 3, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn reallocate_frees_allocated_data_when_it_grows() {
+        let mut rom = build_rom();
+        let address = rom.allocate(&[1; 8]).unwrap();
+        let next_address = rom.allocate(&[4; 4]).unwrap();
+        write_pointer(&mut rom, 0x100, address);
+        // too big for the space before the next allocation
+        let new_address = rom.reallocate(address, 8, &[2; 0x20]).unwrap();
+        assert!(new_address > next_address);
+        assert_eq!(read_at(&mut rom, address, 8), [0xFF; 8]);
+        assert_eq!(read_at(&mut rom, next_address, 4), [4; 4]);
+        assert_eq!(read_at(&mut rom, new_address, 0x20), [2; 0x20]);
+    }
+
+    #[test]
+    fn reallocate_never_frees_vanilla_data() {
+        let mut rom = build_rom();
+        write_pointer(&mut rom, 0x100, 0x200);
+        let new_address = rom.reallocate(0x200, 8, &[2; 12]).unwrap();
+        assert!(rom.is_allocated(new_address));
+        assert_eq!(read_at(&mut rom, 0x200, 8), [0; 8]);
+        assert_eq!(read_at(&mut rom, new_address, 12), [2; 12]);
+    }
+
+    #[test]
+    fn reallocate_keeps_shared_data() {
+        let mut rom = build_rom();
+        let address = rom.allocate(&[1; 8]).unwrap();
+        write_pointer(&mut rom, 0x100, address);
+        write_pointer(&mut rom, 0x104, address);
+        // even when the new data fits, and even if it was allocated
+        let new_address = rom.reallocate(address, 8, &[2; 4]).unwrap();
+        assert_ne!(new_address, address);
+        assert_eq!(read_at(&mut rom, address, 8), [1; 8]);
+        assert_eq!(read_at(&mut rom, new_address, 4), [2; 4]);
+        let new_address = rom.reallocate(address, 8, &[3; 12]).unwrap();
+        assert_eq!(read_at(&mut rom, address, 8), [1; 8]);
+        assert_eq!(read_at(&mut rom, new_address, 12), [3; 12]);
+    }
+
+    const LAYOUT_TABLE: usize = 0x1000;
+    const BANK_TABLE: usize = 0x2000;
+    const BANKS: usize = 0x3000; // 0x200 bytes per bank
+    const LAYOUT: usize = 0x10000; // every layout and map header points here
+
+    // vanilla layout and bank tables (with vanilla sizes) and some free space
+    fn build_map_rom() -> Rom {
+        let mut rom = build_rom_with_free_space(0x1000);
+        write_pointer(&mut rom, MAP_LAYOUT_TABLE_POINTER, LAYOUT_TABLE);
+        for i in 0..NUM_MAP_LAYOUTS_FR {
+            write_pointer(&mut rom, LAYOUT_TABLE + i * 4, LAYOUT);
+        }
+        write_pointer(&mut rom, MAP_BANK_TABLE_POINTER, BANK_TABLE);
+        for (bank_num, &bank_size) in BANK_SIZES_FR.iter().enumerate() {
+            let bank_addr = BANKS + bank_num * 0x200;
+            write_pointer(&mut rom, BANK_TABLE + bank_num * 4, bank_addr);
+            for map_num in 0..bank_size as usize {
+                write_pointer(&mut rom, bank_addr + map_num * 4, LAYOUT);
+            }
+        }
+        rom
+    }
+
+    fn read_pointer(rom: &mut Rom, at: usize) -> usize {
+        rom.seek_to(at).unwrap();
+        rom.read_address().unwrap()
+    }
+
+    #[test]
+    fn append_to_table_moves_and_terminates_tables() {
+        let mut rom = build_rom();
+        for i in 0..3 {
+            write_pointer(&mut rom, 0x100 + i * 4, 0x400 + i * 4);
+        }
+        let vanilla = read_at(&mut rom, 0x100, 12);
+        let table_addr = rom.append_to_table(0x100, 3, 0x500).unwrap();
+        assert!(rom.is_allocated(table_addr));
+        assert_eq!(read_at(&mut rom, 0x100, 12), vanilla);
+        assert_eq!(read_at(&mut rom, table_addr, 12), vanilla);
+        assert_eq!(read_pointer(&mut rom, table_addr + 12), 0x500);
+        assert_eq!(rom.get_table_length(table_addr, 3).unwrap(), 4);
+
+        // the old table and its terminator are freed (so the table grows in
+        // place), and the terminator is rewritten after the new entry
+        let new_table_addr = rom.append_to_table(table_addr, 4, 0x600).unwrap();
+        assert_eq!(new_table_addr, table_addr);
+        assert_eq!(read_at(&mut rom, new_table_addr, 12), vanilla);
+        assert_eq!(read_pointer(&mut rom, new_table_addr + 12), 0x500);
+        assert_eq!(read_pointer(&mut rom, new_table_addr + 16), 0x600);
+        assert_eq!(
+            read_at(&mut rom, new_table_addr + 20, 4),
+            TABLE_TERMINATOR.to_le_bytes()
+        );
+        assert_eq!(read_at(&mut rom, new_table_addr + 24, 4), [0xFF; 4]);
+        assert_eq!(rom.get_table_length(new_table_addr, 3).unwrap(), 5);
+    }
+
+    #[test]
+    fn add_map_repoints_the_bank() {
+        let mut rom = build_map_rom();
+        let vanilla_bank = read_at(&mut rom, BANKS + 3 * 0x200, 66 * 4);
+        assert_eq!(rom.add_map(3, 1, 0x58).unwrap(), (3, 66));
+        assert_eq!(rom.add_map(3, 1, 0x58).unwrap(), (3, 67));
+        assert_eq!(rom.get_bank_size(3).unwrap(), 68);
+        assert_eq!(rom.get_bank_count().unwrap(), BANK_SIZES_FR.len());
+        // only the bank's entry in the table of banks changed
+        assert_eq!(read_pointer(&mut rom, MAP_BANK_TABLE_POINTER), BANK_TABLE);
+        let bank_addr = read_pointer(&mut rom, BANK_TABLE + 3 * 4);
+        assert!(rom.is_allocated(bank_addr));
+        assert_eq!(read_pointer(&mut rom, BANK_TABLE + 4 * 4), BANKS + 0x800);
+        assert_eq!(read_at(&mut rom, BANKS + 3 * 0x200, 66 * 4), vanilla_bank);
+        assert_eq!(read_at(&mut rom, bank_addr, 66 * 4), vanilla_bank);
+        let map_header_addr = read_pointer(&mut rom, bank_addr + 67 * 4);
+        assert_eq!(rom.get_map_header(3, 67).unwrap().map_section, 0x58);
+        assert!(rom.is_allocated(map_header_addr));
+    }
+
+    #[test]
+    fn add_bank_repoints_the_table_of_banks() {
+        let mut rom = build_map_rom();
+        let vanilla_banks = read_at(&mut rom, BANK_TABLE, 43 * 4);
+        assert_eq!(rom.add_bank(1, 0x58).unwrap(), (43, 0));
+        assert_eq!(rom.add_map(43, 1, 0x59).unwrap(), (43, 1));
+        assert_eq!(rom.add_bank(1, 0x5A).unwrap(), (44, 0));
+        assert_eq!(rom.get_bank_count().unwrap(), 45);
+        assert_eq!(rom.get_bank_size(43).unwrap(), 2);
+        assert_eq!(rom.get_bank_size(44).unwrap(), 1);
+        let bank_table_addr = read_pointer(&mut rom, MAP_BANK_TABLE_POINTER);
+        assert!(rom.is_allocated(bank_table_addr));
+        assert_eq!(read_at(&mut rom, BANK_TABLE, 43 * 4), vanilla_banks);
+        assert_eq!(read_at(&mut rom, bank_table_addr, 43 * 4), vanilla_banks);
+        assert_eq!(rom.get_map_header(44, 0).unwrap().map_section, 0x5A);
+        assert!(rom.get_map_header(45, 0).is_err());
+    }
+
+    #[test]
+    fn unterminated_tables_use_the_vanilla_length() {
+        let mut rom = build_map_rom();
+        // bank 3 moved by another tool, right before other pointers and data
+        let bank_addr = FREE_SPACE_START + 0x800;
+        for map_num in 0..68 {
+            write_pointer(&mut rom, bank_addr + map_num * 4, LAYOUT);
+        }
+        rom.seek_to(bank_addr + 68 * 4).unwrap();
+        rom.write_u32(0x12345678).unwrap();
+        write_pointer(&mut rom, BANK_TABLE + 3 * 4, bank_addr);
+        assert_eq!(rom.get_bank_size(3).unwrap(), 66);
+
+        assert_eq!(rom.add_map(3, 1, 0x58).unwrap(), (3, 66));
+        assert_eq!(rom.get_bank_size(3).unwrap(), 67);
+        // only the vanilla length was freed
+        assert_eq!(read_at(&mut rom, bank_addr, 66 * 4), [0xFF; 66 * 4]);
+        assert_eq!(read_pointer(&mut rom, bank_addr + 66 * 4), LAYOUT);
+        assert_eq!(read_pointer(&mut rom, bank_addr + 67 * 4), LAYOUT);
+        assert_eq!(
+            read_at(&mut rom, bank_addr + 68 * 4, 4),
+            0x12345678u32.to_le_bytes()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::species::*;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct Stats {
+    pub hp: u8,
+    pub attack: u8,
+    pub defense: u8,
+    pub speed: u8,
+    pub sp_attack: u8,
+    pub sp_defense: u8,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BaseStats {
+    pub stats: Stats,
+    pub types: [PokemonType; 2],
+    pub catch_rate: u8,
+    pub exp_yield: u8,
+    pub ev_yield: Stats,      // 2 bits per stat
+    pub held_items: [u16; 2], // common (50%), rare (5%)
+    pub gender_ratio: u8, // 0: male only; 0xFE: female only; 0xFF: genderless
+    pub egg_cycles: u8,
+    pub friendship: u8,
+    pub growth_rate: u8,
+    pub egg_groups: [u8; 2],
+    pub abilities: [u8; 2],
+    pub safari_zone_flee_rate: u8,
+    pub body_color: u8, // 0 - 6
+    pub no_flip: bool,  // 7
+    #[serde(skip_serializing, skip_deserializing)]
+    _padding: u16,
+}
+
+impl BaseStats {
+    pub fn read(address: usize, rom: &mut Rom) -> Result<BaseStats> {
+        rom.seek_to(address)?;
+        let stats = Stats {
+            hp: rom.read_u8()?,
+            attack: rom.read_u8()?,
+            defense: rom.read_u8()?,
+            speed: rom.read_u8()?,
+            sp_attack: rom.read_u8()?,
+            sp_defense: rom.read_u8()?,
+        };
+        let types = [
+            PokemonType::new(rom.read_u8()?),
+            PokemonType::new(rom.read_u8()?),
+        ];
+        let catch_rate = rom.read_u8()?;
+        let exp_yield = rom.read_u8()?;
+        let ev = rom.read_u16()?;
+        let ev_yield = Stats {
+            hp: (ev & 3) as u8,
+            attack: ((ev >> 2) & 3) as u8,
+            defense: ((ev >> 4) & 3) as u8,
+            speed: ((ev >> 6) & 3) as u8,
+            sp_attack: ((ev >> 8) & 3) as u8,
+            sp_defense: ((ev >> 10) & 3) as u8,
+        };
+        let held_items = [rom.read_u16()?, rom.read_u16()?];
+        let gender_ratio = rom.read_u8()?;
+        let egg_cycles = rom.read_u8()?;
+        let friendship = rom.read_u8()?;
+        let growth_rate = rom.read_u8()?;
+        let egg_groups = [rom.read_u8()?, rom.read_u8()?];
+        let abilities = [rom.read_u8()?, rom.read_u8()?];
+        let safari_zone_flee_rate = rom.read_u8()?;
+        let color = rom.read_u8()?;
+        Ok(BaseStats {
+            stats,
+            types,
+            catch_rate,
+            exp_yield,
+            ev_yield,
+            held_items,
+            gender_ratio,
+            egg_cycles,
+            friendship,
+            growth_rate,
+            egg_groups,
+            abilities,
+            safari_zone_flee_rate,
+            body_color: color & 0x7F,
+            no_flip: (color >> 7) != 0,
+            _padding: rom.read_u16()?,
+        })
+    }
+    pub fn write(&self, address: usize, rom: &mut Rom) -> Result<()> {
+        rom.seek_to(address)?;
+        rom.write_u8(self.stats.hp)?;
+        rom.write_u8(self.stats.attack)?;
+        rom.write_u8(self.stats.defense)?;
+        rom.write_u8(self.stats.speed)?;
+        rom.write_u8(self.stats.sp_attack)?;
+        rom.write_u8(self.stats.sp_defense)?;
+        rom.write_u8(self.types[0].value())?;
+        rom.write_u8(self.types[1].value())?;
+        rom.write_u8(self.catch_rate)?;
+        rom.write_u8(self.exp_yield)?;
+        let ev = (self.ev_yield.hp as u16 & 3)
+            | (self.ev_yield.attack as u16 & 3) << 2
+            | (self.ev_yield.defense as u16 & 3) << 4
+            | (self.ev_yield.speed as u16 & 3) << 6
+            | (self.ev_yield.sp_attack as u16 & 3) << 8
+            | (self.ev_yield.sp_defense as u16 & 3) << 10;
+        rom.write_u16(ev)?;
+        rom.write_u16(self.held_items[0])?;
+        rom.write_u16(self.held_items[1])?;
+        rom.write_u8(self.gender_ratio)?;
+        rom.write_u8(self.egg_cycles)?;
+        rom.write_u8(self.friendship)?;
+        rom.write_u8(self.growth_rate)?;
+        rom.write_u8(self.egg_groups[0])?;
+        rom.write_u8(self.egg_groups[1])?;
+        rom.write_u8(self.abilities[0])?;
+        rom.write_u8(self.abilities[1])?;
+        rom.write_u8(self.safari_zone_flee_rate)?;
+        rom.write_u8((self.body_color & 0x7F) | (self.no_flip as u8) << 7)?;
+        rom.write_u16(self._padding)
+    }
+}
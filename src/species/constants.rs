@@ -0,0 +1,14 @@
+pub const NUM_SPECIES: usize = 412;
+
+pub const SIZE_BASE_STATS: usize = 28;
+pub const SIZE_SPECIES_NAME: usize = 11;
+
+pub const NUM_TMS: usize = 50;
+pub const NUM_HMS: usize = 8;
+
+pub const NUM_EVOLUTIONS_PER_SPECIES: usize = 5;
+pub const SIZE_EVOLUTION: usize = 8;
+
+pub const SIZE_POKEMON_PIC: usize = 64;
+pub const SIZE_ICON: usize = 32;
+pub const NUM_ICON_FRAMES: usize = 2;
@@ -0,0 +1,201 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::species::constants::*;
+
+const EVOLUTION_TABLE_ADDRESS: usize = 0x259754;
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EvolutionMethod {
+    #[default]
+    None,
+    Friendship,
+    FriendshipDay,
+    FriendshipNight,
+    Level,
+    Trade,
+    TradeItem,
+    Item,
+    LevelAtkGtDef,
+    LevelAtkEqDef,
+    LevelAtkLtDef,
+    LevelSilcoon,
+    LevelCascoon,
+    LevelNinjask,
+    LevelShedinja,
+    Beauty,
+    Unknown(u16),
+}
+
+impl EvolutionMethod {
+    pub fn new(value: u16) -> EvolutionMethod {
+        match value {
+            0x0 => EvolutionMethod::None,
+            0x1 => EvolutionMethod::Friendship,
+            0x2 => EvolutionMethod::FriendshipDay,
+            0x3 => EvolutionMethod::FriendshipNight,
+            0x4 => EvolutionMethod::Level,
+            0x5 => EvolutionMethod::Trade,
+            0x6 => EvolutionMethod::TradeItem,
+            0x7 => EvolutionMethod::Item,
+            0x8 => EvolutionMethod::LevelAtkGtDef,
+            0x9 => EvolutionMethod::LevelAtkEqDef,
+            0xA => EvolutionMethod::LevelAtkLtDef,
+            0xB => EvolutionMethod::LevelSilcoon,
+            0xC => EvolutionMethod::LevelCascoon,
+            0xD => EvolutionMethod::LevelNinjask,
+            0xE => EvolutionMethod::LevelShedinja,
+            0xF => EvolutionMethod::Beauty,
+            _ => EvolutionMethod::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u16 {
+        match self {
+            EvolutionMethod::None => 0x0,
+            EvolutionMethod::Friendship => 0x1,
+            EvolutionMethod::FriendshipDay => 0x2,
+            EvolutionMethod::FriendshipNight => 0x3,
+            EvolutionMethod::Level => 0x4,
+            EvolutionMethod::Trade => 0x5,
+            EvolutionMethod::TradeItem => 0x6,
+            EvolutionMethod::Item => 0x7,
+            EvolutionMethod::LevelAtkGtDef => 0x8,
+            EvolutionMethod::LevelAtkEqDef => 0x9,
+            EvolutionMethod::LevelAtkLtDef => 0xA,
+            EvolutionMethod::LevelSilcoon => 0xB,
+            EvolutionMethod::LevelCascoon => 0xC,
+            EvolutionMethod::LevelNinjask => 0xD,
+            EvolutionMethod::LevelShedinja => 0xE,
+            EvolutionMethod::Beauty => 0xF,
+            EvolutionMethod::Unknown(value) => *value,
+        }
+    }
+}
+
+impl fmt::Display for EvolutionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvolutionMethod::None => write!(f, "None"),
+            EvolutionMethod::Friendship => write!(f, "High friendship"),
+            EvolutionMethod::FriendshipDay => {
+                write!(f, "High friendship (day)")
+            }
+            EvolutionMethod::FriendshipNight => {
+                write!(f, "High friendship (night)")
+            }
+            EvolutionMethod::Level => write!(f, "Level up"),
+            EvolutionMethod::Trade => write!(f, "Trade"),
+            EvolutionMethod::TradeItem => write!(f, "Trade holding item"),
+            EvolutionMethod::Item => write!(f, "Use item"),
+            EvolutionMethod::LevelAtkGtDef => {
+                write!(f, "Level up (Attack > Defense)")
+            }
+            EvolutionMethod::LevelAtkEqDef => {
+                write!(f, "Level up (Attack = Defense)")
+            }
+            EvolutionMethod::LevelAtkLtDef => {
+                write!(f, "Level up (Attack < Defense)")
+            }
+            EvolutionMethod::LevelSilcoon => {
+                write!(f, "Level up (personality-based, Silcoon)")
+            }
+            EvolutionMethod::LevelCascoon => {
+                write!(f, "Level up (personality-based, Cascoon)")
+            }
+            EvolutionMethod::LevelNinjask => write!(f, "Level up (Ninjask)"),
+            EvolutionMethod::LevelShedinja => {
+                write!(f, "Level up (free party slot, Shedinja)")
+            }
+            EvolutionMethod::Beauty => write!(f, "High beauty"),
+            EvolutionMethod::Unknown(value) => {
+                write!(f, "Unknown method ({:#x})", value)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct Evolution {
+    pub method: EvolutionMethod,
+    pub parameter: u16, // level, item or beauty, depending on `method`
+    pub target_species: u16,
+    #[serde(skip_serializing, skip_deserializing)]
+    _padding: u16,
+}
+
+impl Evolution {
+    pub fn read(address: usize, rom: &mut Rom) -> Result<Evolution> {
+        rom.seek_to(address)?;
+        Ok(Evolution {
+            method: EvolutionMethod::new(rom.read_u16()?),
+            parameter: rom.read_u16()?,
+            target_species: rom.read_u16()?,
+            _padding: rom.read_u16()?,
+        })
+    }
+    pub fn write(&self, address: usize, rom: &mut Rom) -> Result<()> {
+        rom.seek_to(address)?;
+        rom.write_u16(self.method.value())?;
+        rom.write_u16(self.parameter)?;
+        rom.write_u16(self.target_species)?;
+        rom.write_u16(self._padding)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Evolutions {
+    pub species_num: u16,
+    pub slots: [Evolution; NUM_EVOLUTIONS_PER_SPECIES],
+}
+
+impl Evolutions {
+    pub fn get(rom: &mut Rom, species_num: u16) -> Result<Evolutions> {
+        let address = Evolutions::get_address(species_num);
+        let mut read_slot = |slot_num: usize| {
+            Evolution::read(address + slot_num * SIZE_EVOLUTION, rom)
+        };
+        Ok(Evolutions {
+            species_num,
+            slots: [
+                read_slot(0)?,
+                read_slot(1)?,
+                read_slot(2)?,
+                read_slot(3)?,
+                read_slot(4)?,
+            ],
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Evolutions>> {
+        let mut all_evolutions = vec![];
+        for species_num in 0..NUM_SPECIES {
+            all_evolutions.push(Evolutions::get(rom, species_num as u16)?);
+        }
+        Ok(all_evolutions)
+    }
+    // the non-empty slots
+    pub fn get_evolutions(&self) -> Vec<Evolution> {
+        self.slots
+            .iter()
+            .filter(|evolution| evolution.method != EvolutionMethod::None)
+            .copied()
+            .collect()
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        let address = Evolutions::get_address(self.species_num);
+        for (slot_num, evolution) in self.slots.iter().enumerate() {
+            evolution.write(address + slot_num * SIZE_EVOLUTION, rom)?;
+        }
+        Ok(())
+    }
+
+    fn get_address(species_num: u16) -> usize {
+        EVOLUTION_TABLE_ADDRESS
+            + species_num as usize * SIZE_EVOLUTION * NUM_EVOLUTIONS_PER_SPECIES
+    }
+}
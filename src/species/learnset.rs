@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::species::constants::*;
+
+const LEVEL_UP_LEARNSETS_ADDRESS: usize = 0x25D7B4;
+const LEARNSET_TERMINATOR: u16 = 0xFFFF;
+
+#[derive(Debug)]
+pub enum InvalidLearnsetMove {
+    InvalidMoveId(u16),
+    InvalidLevel(u8),
+    IsTerminator,
+}
+impl std::error::Error for InvalidLearnsetMove {}
+impl fmt::Display for InvalidLearnsetMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidLearnsetMove::InvalidMoveId(move_id) => {
+                write!(f, "Move {:#x} is too high (max 0x1ff)!", move_id)
+            }
+            InvalidLearnsetMove::InvalidLevel(level) => {
+                write!(f, "Level {} is too high (max 127)!", level)
+            }
+            InvalidLearnsetMove::IsTerminator => {
+                write!(f, "Move 0x1ff at level 127 is the list terminator!")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct LearnsetMove {
+    pub move_id: u16, // 0 - 8
+    pub level: u8,    // 9 - F
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Learnset {
+    pub species_num: u16,
+    pub moves: Vec<LearnsetMove>,
+}
+
+impl LearnsetMove {
+    pub fn value(&self) -> Result<u16> {
+        if self.move_id > 0x1FF {
+            Err(InvalidLearnsetMove::InvalidMoveId(self.move_id))?;
+        }
+        if self.level > 0x7F {
+            Err(InvalidLearnsetMove::InvalidLevel(self.level))?;
+        }
+        let value = self.move_id | (self.level as u16) << 9;
+        if value == LEARNSET_TERMINATOR {
+            Err(InvalidLearnsetMove::IsTerminator)?;
+        }
+        Ok(value)
+    }
+}
+
+impl Learnset {
+    pub fn get(rom: &mut Rom, species_num: u16) -> Result<Learnset> {
+        let address = Learnset::get_address(rom, species_num)?;
+        Ok(Learnset {
+            species_num,
+            moves: Learnset::read_moves(address, rom)?,
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Learnset>> {
+        let mut learnsets = vec![];
+        for species_num in 0..NUM_SPECIES {
+            learnsets.push(Learnset::get(rom, species_num as u16)?);
+        }
+        Ok(learnsets)
+    }
+    // learnsets are written back in place when they fit, otherwise they are
+    // moved to free space and the learnset pointer is updated
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        let pointer_addr =
+            LEVEL_UP_LEARNSETS_ADDRESS + self.species_num as usize * 4;
+        let old_address = Learnset::get_address(rom, self.species_num)?;
+        let old_length =
+            (Learnset::read_moves(old_address, rom)?.len() + 1) * 2;
+
+        let mut data = vec![];
+        for learnset_move in &self.moves {
+            data.extend(learnset_move.value()?.to_le_bytes());
+        }
+        data.extend(LEARNSET_TERMINATOR.to_le_bytes());
+
+        let address = rom.reallocate(old_address, old_length, &data)?;
+        rom.seek_to(pointer_addr)?;
+        rom.write_address(address)
+    }
+
+    fn get_address(rom: &mut Rom, species_num: u16) -> Result<usize> {
+        rom.seek_to(LEVEL_UP_LEARNSETS_ADDRESS + species_num as usize * 4)?;
+        rom.read_address()
+    }
+    fn read_moves(address: usize, rom: &mut Rom) -> Result<Vec<LearnsetMove>> {
+        rom.seek_to(address)?;
+        let mut moves = vec![];
+        loop {
+            let value = rom.read_u16()?;
+            if value == LEARNSET_TERMINATOR {
+                return Ok(moves);
+            }
+            moves.push(LearnsetMove {
+                move_id: value & 0x1FF,
+                level: (value >> 9) as u8,
+            });
+        }
+    }
+}
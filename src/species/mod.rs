@@ -0,0 +1,22 @@
+pub mod constants;
+
+mod base_stats;
+pub use self::base_stats::*;
+
+mod pokemon_pics;
+pub use self::pokemon_pics::*;
+
+mod pokemon_type;
+pub use self::pokemon_type::*;
+
+mod species_data;
+pub use self::species_data::*;
+
+mod evolution;
+pub use self::evolution::*;
+
+mod learnset;
+pub use self::learnset::*;
+
+mod tm_hm_compatibility;
+pub use self::tm_hm_compatibility::*;
@@ -0,0 +1,212 @@
+use crate::constants::*;
+use crate::error::*;
+use crate::graphics::*;
+use crate::lz77::*;
+use crate::rom::*;
+use crate::species::constants::*;
+
+const FRONT_PIC_TABLE_ADDRESS: usize = 0x2350AC;
+const BACK_PIC_TABLE_ADDRESS: usize = 0x23654C;
+const PALETTE_TABLE_ADDRESS: usize = 0x23730C;
+const SHINY_PALETTE_TABLE_ADDRESS: usize = 0x2380CC;
+const ICON_TABLE_ADDRESS: usize = 0x3D37A0;
+const ICON_PALETTE_INDICES_ADDRESS: usize = 0x3D3E80;
+const ICON_PALETTE_TABLE_ADDRESS: usize = 0x3D4038;
+
+// front/back pics, palettes and icons of a species; sprite sheet and palette
+// table entries are 8 bytes (address, size/tag, tag/padding)
+pub struct PokemonPics {
+    pub species_num: u16,
+    pub front_pic_addr: usize,
+    pub back_pic_addr: usize,
+    pub palette_addr: usize,
+    pub shiny_palette_addr: usize,
+    pub icon_addr: usize,
+    pub icon_palette_index: u8,
+}
+
+impl PokemonPics {
+    pub fn get(rom: &mut Rom, species_num: u16) -> Result<PokemonPics> {
+        let species = species_num as usize;
+        let mut read_entry = |table_address: usize, entry_size: usize| {
+            rom.seek_to(table_address + species * entry_size)?;
+            rom.read_address()
+        };
+        let front_pic_addr = read_entry(FRONT_PIC_TABLE_ADDRESS, 8)?;
+        let back_pic_addr = read_entry(BACK_PIC_TABLE_ADDRESS, 8)?;
+        let palette_addr = read_entry(PALETTE_TABLE_ADDRESS, 8)?;
+        let shiny_palette_addr = read_entry(SHINY_PALETTE_TABLE_ADDRESS, 8)?;
+        let icon_addr = read_entry(ICON_TABLE_ADDRESS, 4)?;
+        rom.seek_to(ICON_PALETTE_INDICES_ADDRESS + species)?;
+        Ok(PokemonPics {
+            species_num,
+            front_pic_addr,
+            back_pic_addr,
+            palette_addr,
+            shiny_palette_addr,
+            icon_addr,
+            icon_palette_index: rom.read_u8()?,
+        })
+    }
+    pub fn get_palette(
+        &self,
+        rom: &mut Rom,
+        shiny: bool,
+    ) -> Result<[Color; NUM_COLORS_IN_PALETTE]> {
+        let address = match shiny {
+            true => self.shiny_palette_addr,
+            false => self.palette_addr,
+        };
+        let data = lz77_decompress(address, rom)?;
+        Ok(read_palette(&data))
+    }
+    pub fn get_front_pic(&self, rom: &mut Rom, shiny: bool) -> Result<Image> {
+        self.get_pic(rom, self.front_pic_addr, shiny)
+    }
+    pub fn get_back_pic(&self, rom: &mut Rom, shiny: bool) -> Result<Image> {
+        self.get_pic(rom, self.back_pic_addr, shiny)
+    }
+    pub fn get_icon_palette(
+        &self,
+        rom: &mut Rom,
+    ) -> Result<[Color; NUM_COLORS_IN_PALETTE]> {
+        rom.seek_to(
+            ICON_PALETTE_TABLE_ADDRESS + self.icon_palette_index as usize * 8,
+        )?;
+        rom.seek_to_address_read()?;
+        let data = rom.read_data(NUM_COLORS_IN_PALETTE * 2);
+        Ok(read_palette(&data))
+    }
+    // both animation frames, one above the other
+    pub fn get_icon(&self, rom: &mut Rom) -> Result<Image> {
+        let palette = self.get_icon_palette(rom)?;
+        rom.seek_to(self.icon_addr)?;
+        let data = rom.read_data(SIZE_ICON * SIZE_ICON * NUM_ICON_FRAMES / 2);
+        Ok(Image::from_tiles(
+            &data,
+            SIZE_ICON,
+            SIZE_ICON * NUM_ICON_FRAMES,
+            palette.to_vec(),
+        ))
+    }
+
+    pub fn set_front_pic(
+        &mut self,
+        rom: &mut Rom,
+        image: &Image,
+    ) -> Result<()> {
+        self.front_pic_addr = self.set_pic(
+            rom,
+            FRONT_PIC_TABLE_ADDRESS,
+            self.front_pic_addr,
+            image,
+        )?;
+        Ok(())
+    }
+    pub fn set_back_pic(&mut self, rom: &mut Rom, image: &Image) -> Result<()> {
+        self.back_pic_addr = self.set_pic(
+            rom,
+            BACK_PIC_TABLE_ADDRESS,
+            self.back_pic_addr,
+            image,
+        )?;
+        Ok(())
+    }
+    pub fn set_palette(
+        &mut self,
+        rom: &mut Rom,
+        palette: &[Color],
+        shiny: bool,
+    ) -> Result<()> {
+        let (table_address, old_address) = match shiny {
+            true => (SHINY_PALETTE_TABLE_ADDRESS, self.shiny_palette_addr),
+            false => (PALETTE_TABLE_ADDRESS, self.palette_addr),
+        };
+        let data = lz77_compress(&write_palette(palette));
+        let address =
+            self.write_compressed(rom, table_address, old_address, &data)?;
+        match shiny {
+            true => self.shiny_palette_addr = address,
+            false => self.palette_addr = address,
+        }
+        Ok(())
+    }
+    // imports a PNG as front or back pic along with its (normal) palette
+    pub fn import_pic(
+        &mut self,
+        rom: &mut Rom,
+        png_data: &[u8],
+        back: bool,
+        quantize: bool,
+    ) -> Result<()> {
+        let image = Image::from_png(png_data, quantize)?;
+        match back {
+            true => self.set_back_pic(rom, &image)?,
+            false => self.set_front_pic(rom, &image)?,
+        }
+        self.set_palette(rom, &image.palette, false)
+    }
+
+    fn get_pic(
+        &self,
+        rom: &mut Rom,
+        address: usize,
+        shiny: bool,
+    ) -> Result<Image> {
+        let palette = self.get_palette(rom, shiny)?;
+        let data = lz77_decompress(address, rom)?;
+        Ok(Image::from_tiles(
+            &data,
+            SIZE_POKEMON_PIC,
+            SIZE_POKEMON_PIC,
+            palette.to_vec(),
+        ))
+    }
+    fn set_pic(
+        &self,
+        rom: &mut Rom,
+        table_address: usize,
+        old_address: usize,
+        image: &Image,
+    ) -> Result<usize> {
+        image.check_dimensions(SIZE_POKEMON_PIC, SIZE_POKEMON_PIC)?;
+        let data = lz77_compress(&image.to_tiles());
+        self.write_compressed(rom, table_address, old_address, &data)
+    }
+    // compressed data is written back in place when it fits (and isn't shared
+    // with other species), otherwise it is moved to free space and the table
+    // entry is repointed
+    fn write_compressed(
+        &self,
+        rom: &mut Rom,
+        table_address: usize,
+        old_address: usize,
+        data: &[u8],
+    ) -> Result<usize> {
+        let old_length = lz77_compressed_length(old_address, rom)?;
+        let address = rom.reallocate(old_address, old_length, data)?;
+        rom.seek_to(table_address + self.species_num as usize * 8)?;
+        rom.write_address(address)?;
+        Ok(address)
+    }
+}
+
+fn read_palette(data: &[u8]) -> [Color; NUM_COLORS_IN_PALETTE] {
+    let mut palette = [Color::new(0); NUM_COLORS_IN_PALETTE];
+    for (i, color) in data.chunks(2).take(NUM_COLORS_IN_PALETTE).enumerate() {
+        palette[i] = Color::new(color[0] as u16 | (color[1] as u16) << 8);
+    }
+    palette
+}
+
+fn write_palette(palette: &[Color]) -> Vec<u8> {
+    let mut data = vec![];
+    for i in 0..NUM_COLORS_IN_PALETTE {
+        let value = match palette.get(i) {
+            Some(c) => c.to_bgr555(),
+            None => 0,
+        };
+        data.extend(value.to_le_bytes());
+    }
+    data
+}
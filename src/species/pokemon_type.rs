@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PokemonType {
+    Normal,
+    Fighting,
+    Flying,
+    Poison,
+    Ground,
+    Rock,
+    Bug,
+    Ghost,
+    Steel,
+    Mystery, // the "???" type of Curse
+    Fire,
+    Water,
+    Grass,
+    Electric,
+    Psychic,
+    Ice,
+    Dragon,
+    Dark,
+    Unknown(u8),
+}
+
+impl PokemonType {
+    pub fn new(value: u8) -> PokemonType {
+        match value {
+            0x00 => PokemonType::Normal,
+            0x01 => PokemonType::Fighting,
+            0x02 => PokemonType::Flying,
+            0x03 => PokemonType::Poison,
+            0x04 => PokemonType::Ground,
+            0x05 => PokemonType::Rock,
+            0x06 => PokemonType::Bug,
+            0x07 => PokemonType::Ghost,
+            0x08 => PokemonType::Steel,
+            0x09 => PokemonType::Mystery,
+            0x0A => PokemonType::Fire,
+            0x0B => PokemonType::Water,
+            0x0C => PokemonType::Grass,
+            0x0D => PokemonType::Electric,
+            0x0E => PokemonType::Psychic,
+            0x0F => PokemonType::Ice,
+            0x10 => PokemonType::Dragon,
+            0x11 => PokemonType::Dark,
+            _ => PokemonType::Unknown(value),
+        }
+    }
+    pub fn value(&self) -> u8 {
+        match self {
+            PokemonType::Normal => 0x00,
+            PokemonType::Fighting => 0x01,
+            PokemonType::Flying => 0x02,
+            PokemonType::Poison => 0x03,
+            PokemonType::Ground => 0x04,
+            PokemonType::Rock => 0x05,
+            PokemonType::Bug => 0x06,
+            PokemonType::Ghost => 0x07,
+            PokemonType::Steel => 0x08,
+            PokemonType::Mystery => 0x09,
+            PokemonType::Fire => 0x0A,
+            PokemonType::Water => 0x0B,
+            PokemonType::Grass => 0x0C,
+            PokemonType::Electric => 0x0D,
+            PokemonType::Psychic => 0x0E,
+            PokemonType::Ice => 0x0F,
+            PokemonType::Dragon => 0x10,
+            PokemonType::Dark => 0x11,
+            PokemonType::Unknown(value) => *value,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::species::{constants::*, *};
+use crate::text::*;
+
+const BASE_STATS_ADDRESS: usize = 0x254784;
+const SPECIES_NAMES_ADDRESS: usize = 0x245EE0;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Species {
+    pub species_num: u16,
+    pub name: String,
+    pub base_stats: BaseStats,
+}
+
+impl Species {
+    pub fn get(rom: &mut Rom, species_num: u16) -> Result<Species> {
+        Ok(Species {
+            species_num,
+            name: Species::get_name(rom, species_num)?,
+            base_stats: BaseStats::read(
+                Species::get_base_stats_address(species_num),
+                rom,
+            )?,
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Species>> {
+        let mut all_species = vec![];
+        for species_num in 0..NUM_SPECIES {
+            all_species.push(Species::get(rom, species_num as u16)?);
+        }
+        Ok(all_species)
+    }
+    pub fn get_name(rom: &mut Rom, species_num: u16) -> Result<String> {
+        read_text(
+            SPECIES_NAMES_ADDRESS + species_num as usize * SIZE_SPECIES_NAME,
+            rom,
+            SIZE_SPECIES_NAME,
+        )
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        write_text(
+            SPECIES_NAMES_ADDRESS
+                + self.species_num as usize * SIZE_SPECIES_NAME,
+            rom,
+            &self.name,
+            SIZE_SPECIES_NAME,
+        )?;
+        self.base_stats
+            .write(Species::get_base_stats_address(self.species_num), rom)
+    }
+
+    fn get_base_stats_address(species_num: u16) -> usize {
+        BASE_STATS_ADDRESS + species_num as usize * SIZE_BASE_STATS
+    }
+}
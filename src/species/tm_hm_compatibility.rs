@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::species::constants::*;
+
+const TM_HM_LEARNSETS_ADDRESS: usize = 0x252BC8;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmHmCompatibility {
+    pub species_num: u16,
+    pub tms: Vec<u8>, // TM numbers (1 - 50) the species can learn
+    pub hms: Vec<u8>, // HM numbers (1 - 8) the species can learn
+}
+
+impl TmHmCompatibility {
+    pub fn get(rom: &mut Rom, species_num: u16) -> Result<TmHmCompatibility> {
+        rom.seek_to(TmHmCompatibility::get_address(species_num))?;
+        let value = rom.read_u32()? as u64 | (rom.read_u32()? as u64) << 32;
+        let is_set = |bit: usize| (value >> bit) & 1 != 0;
+        Ok(TmHmCompatibility {
+            species_num,
+            tms: (0..NUM_TMS)
+                .filter(|tm| is_set(*tm))
+                .map(|tm| tm as u8 + 1)
+                .collect(),
+            hms: (0..NUM_HMS)
+                .filter(|hm| is_set(NUM_TMS + *hm))
+                .map(|hm| hm as u8 + 1)
+                .collect(),
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<TmHmCompatibility>> {
+        let mut compatibilities = vec![];
+        for species_num in 0..NUM_SPECIES {
+            compatibilities
+                .push(TmHmCompatibility::get(rom, species_num as u16)?);
+        }
+        Ok(compatibilities)
+    }
+    pub fn can_learn_tm(&self, tm: u8) -> bool {
+        self.tms.contains(&tm)
+    }
+    pub fn can_learn_hm(&self, hm: u8) -> bool {
+        self.hms.contains(&hm)
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        let mut value = 0u64;
+        for tm in self
+            .tms
+            .iter()
+            .filter(|tm| (1..=NUM_TMS as u8).contains(tm))
+        {
+            value |= 1 << (tm - 1);
+        }
+        for hm in self
+            .hms
+            .iter()
+            .filter(|hm| (1..=NUM_HMS as u8).contains(hm))
+        {
+            value |= 1 << (NUM_TMS as u8 + hm - 1);
+        }
+        rom.seek_to(TmHmCompatibility::get_address(self.species_num))?;
+        rom.write_u32(value as u32)?;
+        rom.write_u32((value >> 32) as u32)
+    }
+
+    fn get_address(species_num: u16) -> usize {
+        TM_HM_LEARNSETS_ADDRESS + species_num as usize * 8
+    }
+}
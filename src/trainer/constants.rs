@@ -0,0 +1,9 @@
+pub const NUM_TRAINERS: usize = 743;
+pub const NUM_TRAINER_ITEMS: usize = 4;
+pub const NUM_PARTY_MEMBER_MOVES: usize = 4;
+pub const MAX_PARTY_SIZE: usize = 6;
+
+pub const SIZE_TRAINER: usize = 40;
+pub const SIZE_TRAINER_NAME: usize = 12;
+pub const SIZE_PARTY_MEMBER: usize = 8;
+pub const SIZE_PARTY_MEMBER_CUSTOM_MOVES: usize = 16;
@@ -0,0 +1,7 @@
+pub mod constants;
+
+mod trainer_data;
+pub use self::trainer_data::*;
+
+mod trainer_party;
+pub use self::trainer_party::*;
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::rom::*;
+use crate::text::*;
+use crate::trainer::{constants::*, *};
+
+const TRAINERS_ADDRESS: usize = 0x23EAC8;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Trainer {
+    pub trainer_num: u16,
+    pub has_custom_moves: bool, // party flags: 0
+    pub has_held_items: bool,   // party flags: 1
+    pub trainer_class: u8,
+    pub encounter_music: u8, // 0 - 6
+    pub is_female: bool,     // 7
+    pub trainer_pic: u8,
+    pub name: String,
+    pub items: [u16; NUM_TRAINER_ITEMS],
+    pub is_double_battle: bool,
+    pub ai_flags: u32,
+    pub party_size: u8,
+    pub party_addr: usize,
+}
+
+impl Trainer {
+    pub fn get(rom: &mut Rom, trainer_num: u16) -> Result<Trainer> {
+        let address = Trainer::get_address(trainer_num);
+        rom.seek_to(address)?;
+        let party_flags = rom.read_u8()?;
+        let trainer_class = rom.read_u8()?;
+        let music = rom.read_u8()?;
+        let trainer_pic = rom.read_u8()?;
+        let name = read_text(address + 4, rom, SIZE_TRAINER_NAME)?;
+        rom.seek_to(address + 4 + SIZE_TRAINER_NAME)?;
+        let mut items = [0; NUM_TRAINER_ITEMS];
+        for item in items.iter_mut() {
+            *item = rom.read_u16()?;
+        }
+        let is_double_battle = rom.read_u32()? & 0xFF != 0;
+        let ai_flags = rom.read_u32()?;
+        let party_size = (rom.read_u32()? & 0xFF) as u8;
+        Ok(Trainer {
+            trainer_num,
+            has_custom_moves: party_flags & 1 != 0,
+            has_held_items: (party_flags >> 1) & 1 != 0,
+            trainer_class,
+            encounter_music: music & 0x7F,
+            is_female: (music >> 7) != 0,
+            trainer_pic,
+            name,
+            items,
+            is_double_battle,
+            ai_flags,
+            party_size,
+            party_addr: rom.read_address()?,
+        })
+    }
+    pub fn get_all(rom: &mut Rom) -> Result<Vec<Trainer>> {
+        let mut trainers = vec![];
+        for trainer_num in 0..NUM_TRAINERS {
+            trainers.push(Trainer::get(rom, trainer_num as u16)?);
+        }
+        Ok(trainers)
+    }
+    pub fn write(&self, rom: &mut Rom) -> Result<()> {
+        let address = Trainer::get_address(self.trainer_num);
+        rom.seek_to(address)?;
+        rom.write_u8(
+            self.has_custom_moves as u8 | (self.has_held_items as u8) << 1,
+        )?;
+        rom.write_u8(self.trainer_class)?;
+        rom.write_u8(
+            (self.encounter_music & 0x7F) | (self.is_female as u8) << 7,
+        )?;
+        rom.write_u8(self.trainer_pic)?;
+        write_text(address + 4, rom, &self.name, SIZE_TRAINER_NAME)?;
+        rom.seek_to(address + 4 + SIZE_TRAINER_NAME)?;
+        for item in self.items {
+            rom.write_u16(item)?;
+        }
+        rom.write_u32(self.is_double_battle as u32)?;
+        rom.write_u32(self.ai_flags)?;
+        rom.write_u32(self.party_size as u32)?;
+        rom.write_address(self.party_addr)
+    }
+    pub fn get_party(&self, rom: &mut Rom) -> Result<Vec<TrainerPartyMember>> {
+        let mut party = vec![];
+        for i in 0..self.party_size as usize {
+            party.push(TrainerPartyMember::read(
+                self.party_addr + i * self.get_party_member_size(),
+                rom,
+                self.has_held_items,
+                self.has_custom_moves,
+            )?);
+        }
+        Ok(party)
+    }
+    // the party is written back in place when it fits, otherwise it is moved to
+    // free space; the trainer (with its new party size, address and flags) is
+    // then written back as well; fails if the party is empty or has more than
+    // `MAX_PARTY_SIZE` members
+    pub fn set_party(
+        &mut self,
+        rom: &mut Rom,
+        party: &[TrainerPartyMember],
+        has_held_items: bool,
+        has_custom_moves: bool,
+    ) -> Result<()> {
+        if !(1..=MAX_PARTY_SIZE).contains(&party.len()) {
+            return Err(Error::InvalidPartySize(party.len()));
+        }
+        let old_length =
+            self.party_size as usize * self.get_party_member_size();
+        let data = party
+            .iter()
+            .flat_map(|member| {
+                member.to_bytes(has_held_items, has_custom_moves)
+            })
+            .collect::<Vec<u8>>();
+        self.party_addr = rom.reallocate(self.party_addr, old_length, &data)?;
+        self.party_size = party.len() as u8;
+        self.has_held_items = has_held_items;
+        self.has_custom_moves = has_custom_moves;
+        self.write(rom)
+    }
+
+    fn get_address(trainer_num: u16) -> usize {
+        TRAINERS_ADDRESS + trainer_num as usize * SIZE_TRAINER
+    }
+    fn get_party_member_size(&self) -> usize {
+        match self.has_custom_moves {
+            true => SIZE_PARTY_MEMBER_CUSTOM_MOVES,
+            false => SIZE_PARTY_MEMBER,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_party_rejects_invalid_sizes() {
+        let mut rom = Rom::new(vec![0; 0x100]);
+        let mut trainer = Trainer {
+            trainer_num: 1,
+            has_custom_moves: false,
+            has_held_items: false,
+            trainer_class: 0,
+            encounter_music: 0,
+            is_female: false,
+            trainer_pic: 0,
+            name: String::new(),
+            items: [0; NUM_TRAINER_ITEMS],
+            is_double_battle: false,
+            ai_flags: 0,
+            party_size: 1,
+            party_addr: 0x10,
+        };
+        let member = TrainerPartyMember::default();
+        for party_size in [0, MAX_PARTY_SIZE + 1, 0x100] {
+            let party = vec![member; party_size];
+            assert!(matches!(
+                trainer.set_party(&mut rom, &party, true, true),
+                Err(Error::InvalidPartySize(size)) if size == party_size
+            ));
+        }
+        assert_eq!(trainer.party_size, 1);
+        assert!(!trainer.has_held_items && !trainer.has_custom_moves);
+        assert_eq!(rom.get_data(), vec![0; 0x100]);
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::*;
+use crate::item::Item;
+use crate::rom::*;
+use crate::trainer::constants::*;
+
+// all four party formats start with:
+// iv          0x0 - 0x1
+// level       0x2 - 0x3
+// species     0x4 - 0x5
+// followed by:
+// - no held items, default moves: padding 0x6 - 0x7
+// - held items, default moves:    held_item 0x6 - 0x7
+// - no held items, custom moves:  moves 0x6 - 0xD, padding 0xE - 0xF
+// - held items, custom moves:     held_item 0x6 - 0x7, moves 0x8 - 0xF
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct TrainerPartyMember {
+    pub iv: u16,
+    pub level: u16,
+    pub species: u16,
+    pub held_item: u16,
+    pub moves: [u16; NUM_PARTY_MEMBER_MOVES],
+}
+
+impl TrainerPartyMember {
+    pub fn read(
+        address: usize,
+        rom: &mut Rom,
+        has_held_items: bool,
+        has_custom_moves: bool,
+    ) -> Result<TrainerPartyMember> {
+        rom.seek_to(address)?;
+        let mut member = TrainerPartyMember {
+            iv: rom.read_u16()?,
+            level: rom.read_u16()?,
+            species: rom.read_u16()?,
+            ..Default::default()
+        };
+        if has_held_items {
+            member.held_item = rom.read_u16()?;
+        }
+        if has_custom_moves {
+            for i in 0..NUM_PARTY_MEMBER_MOVES {
+                member.moves[i] = rom.read_u16()?;
+            }
+        }
+        Ok(member)
+    }
+    pub fn get_held_item(&self, rom: &mut Rom) -> Result<Item> {
+        Item::get(rom, self.held_item)
+    }
+    pub fn to_bytes(
+        &self,
+        has_held_items: bool,
+        has_custom_moves: bool,
+    ) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend(self.iv.to_le_bytes());
+        data.extend(self.level.to_le_bytes());
+        data.extend(self.species.to_le_bytes());
+        if has_held_items {
+            data.extend(self.held_item.to_le_bytes());
+        }
+        if has_custom_moves {
+            for move_id in self.moves {
+                data.extend(move_id.to_le_bytes());
+            }
+        }
+        // padding (in place of the held item or after the moves)
+        if !has_held_items {
+            data.extend([0, 0]);
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: usize = 0x10;
+
+    #[test]
+    fn to_bytes_read_round_trip() {
+        let member = TrainerPartyMember {
+            iv: 0xFF,
+            level: 42,
+            species: 0x19,
+            held_item: 0x8B,
+            moves: [0x55, 0x56, 0x57, 0x58],
+        };
+        for (has_held_items, has_custom_moves, length) in [
+            (false, false, 0x8),
+            (true, false, 0x8),
+            (false, true, 0x10),
+            (true, true, 0x10),
+        ] {
+            let data = member.to_bytes(has_held_items, has_custom_moves);
+            assert_eq!(data.len(), length);
+            let mut rom_data = vec![0; ADDRESS];
+            rom_data.extend(data);
+            let mut rom = Rom::new(rom_data);
+            let read = TrainerPartyMember::read(
+                ADDRESS,
+                &mut rom,
+                has_held_items,
+                has_custom_moves,
+            )
+            .unwrap();
+            assert_eq!(read.iv, member.iv);
+            assert_eq!(read.level, member.level);
+            assert_eq!(read.species, member.species);
+            match has_held_items {
+                true => assert_eq!(read.held_item, member.held_item),
+                false => assert_eq!(read.held_item, 0),
+            }
+            match has_custom_moves {
+                true => assert_eq!(read.moves, member.moves),
+                false => assert_eq!(read.moves, [0; NUM_PARTY_MEMBER_MOVES]),
+            }
+        }
+    }
+}